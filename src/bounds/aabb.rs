@@ -15,7 +15,11 @@
 
 //! Axis-aligned bounding boxes
 
+use std::iterator::Iterator;
+use std::num::cast;
+
 use math::*;
+use math::plane::{Front, Back, Intersecting};
 
 #[deriving(Clone, Eq)]
 pub struct AABB2<T> {
@@ -43,6 +47,19 @@ impl<T:Clone + Float> AABB2<T> {
     }
 }
 
+/// The kind of feature of an `AABB3` that is closest to a point
+#[deriving(Clone, Eq)]
+pub enum Feature {
+    /// The point lies inside the box
+    Interior,
+    /// The point lies outside one face, within the extent of the other axes
+    Face,
+    /// The point lies outside two faces, nearest an edge
+    Edge,
+    /// The point lies outside three faces, nearest a corner
+    Vertex,
+}
+
 #[deriving(Clone, Eq)]
 pub struct AABB3<T> {
     center: Point3<T>,
@@ -67,4 +84,346 @@ impl<T:Clone + Float> AABB3<T> {
             size: mx - mn,
         }
     }
+
+    /// Returns the corner of the box with the smallest coordinates
+    #[inline]
+    pub fn min(&self) -> Point3<T> {
+        self.center.translate_v(&self.size.div_s(-two!(T)))
+    }
+
+    /// Returns the corner of the box with the largest coordinates
+    #[inline]
+    pub fn max(&self) -> Point3<T> {
+        self.center.translate_v(&self.size.div_s(two!(T)))
+    }
+
+    /// Returns the smallest box containing both `self` and `other`
+    pub fn merge(&self, other: &AABB3<T>) -> AABB3<T> {
+        AABB3::from_bounds(Point3::from_vec3(self.min().as_vec3().min_v(other.min().as_vec3())),
+                           Point3::from_vec3(self.max().as_vec3().max_v(other.max().as_vec3())))
+    }
+
+    /// Computes the box where `self` and `other` overlap
+    ///
+    /// # Return value
+    ///
+    /// - `Some(b)`: The overlapping box `b`. This has a size of zero along
+    ///              any axis on which the boxes only touch.
+    /// - `None`:    The boxes are disjoint.
+    pub fn intersection(&self, other: &AABB3<T>) -> Option<AABB3<T>> {
+        let mn = self.min().as_vec3().max_v(other.min().as_vec3());
+        let mx = self.max().as_vec3().min_v(other.max().as_vec3());
+        if mn.gt_v(&mx).any() {
+            None
+        } else {
+            Some(AABB3::from_bounds(Point3::from_vec3(mn), Point3::from_vec3(mx)))
+        }
+    }
+
+    /// Returns `true` if the boxes overlap or touch
+    pub fn intersects(&self, other: &AABB3<T>) -> bool {
+        self.min().as_vec3().le_v(other.max().as_vec3()).all() &&
+        other.min().as_vec3().le_v(self.max().as_vec3()).all()
+    }
+
+    /// Returns the index of the axis along which the box is largest, where
+    /// `0`, `1` and `2` correspond to the `x`, `y` and `z` axes. Ties are
+    /// resolved in favour of the lower index.
+    pub fn longest_axis(&self) -> uint {
+        if self.size.x >= self.size.y && self.size.x >= self.size.z {
+            0
+        } else if self.size.y >= self.size.z {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Returns the point inside or on the surface of the box that is closest
+    /// to `pos`, which is `pos` itself if it lies inside the box.
+    #[inline]
+    pub fn closest_point(&self, pos: &Point3<T>) -> Point3<T> {
+        Point3::from_vec3(pos.as_vec3().max_v(self.min().as_vec3())
+                                       .min_v(self.max().as_vec3()))
+    }
+
+    /// Determines which kind of feature of the box is closest to `pos`, from
+    /// the number of axes along which `pos` lies outside the box.
+    pub fn closest_feature(&self, pos: &Point3<T>) -> Feature {
+        let (mn, mx) = (self.min(), self.max());
+
+        let mut count = 0;
+        let mut i = 0;
+        while i < 3 {
+            if *pos.i(i) < *mn.i(i) || *pos.i(i) > *mx.i(i) { count += 1; }
+            i += 1;
+        }
+        match count {
+            0 => Interior,
+            1 => Face,
+            2 => Edge,
+            _ => Vertex,
+        }
+    }
+
+    /// Returns an iterator over the points of a regular lattice with spacing
+    /// `step` that lie within the box, starting at `min` and including `max`
+    /// where the size of the box is a multiple of `step`.
+    ///
+    /// The points are ordered with `x` varying fastest, then `y`, then `z`.
+    /// Axes with a zero step are not stepped along, so all points share the
+    /// minimum coordinate on those axes. The components of `step` should not
+    /// be negative.
+    pub fn grid_points(&self, step: &Vec3<T>) -> GridIterator<T> {
+        let mut counts = [1u, ..3];
+        let mut i = 0;
+        while i < 3 {
+            if *step.i(i) > zero!(T) {
+                let n = *self.size.i(i) / *step.i(i);
+                // allow for rounding when the size is a multiple of the step
+                let n = if n.round().approx_eq(&n) { n.round() } else { n.floor() };
+                counts[i] = cast::<T, uint>(n) + 1;
+            }
+            i += 1;
+        }
+        GridIterator { origin: self.min(), step: step.clone(), counts: counts, index: 0 }
+    }
+
+    /// Computes the smallest and largest signed distances from `plane` to
+    /// the corners of the box, as a tuple `(min, max)`. The normal of the
+    /// plane should be of unit length.
+    ///
+    /// Rather than testing all eight corners, this only measures the
+    /// distances to the two corners that lie furthest along and against the
+    /// normal, by projecting the half-extent of the box onto the normal.
+    ///
+    /// This would be `Plane3::signed_distance_aabb`, but `math` cannot depend
+    /// on `bounds`. With the box as the receiver, the name describes the
+    /// range that is returned instead.
+    pub fn signed_distance_range(&self, plane: &Plane3<T>) -> (T, T) {
+        let dist = plane.distance(&self.center);
+        let radius = (plane.normal.x.abs() * self.size.x +
+                      plane.normal.y.abs() * self.size.y +
+                      plane.normal.z.abs() * self.size.z) / two!(T);
+        (dist - radius, dist + radius)
+    }
+
+    /// Determines which side of `plane` the box lies on. The normal of the
+    /// plane should be of unit length.
+    ///
+    /// This is defined here rather than on `Plane3`, alongside
+    /// `Plane3::classify_sphere`, because `math` cannot depend on `bounds`.
+    pub fn classify_plane(&self, plane: &Plane3<T>) -> PlaneSide {
+        let (min, max) = self.signed_distance_range(plane);
+        if min > zero!(T) {
+            Front
+        } else if max < zero!(T) {
+            Back
+        } else {
+            Intersecting
+        }
+    }
+
+    /// Computes where `ray` enters and exits the box, using the slab method
+    ///
+    /// # Return value
+    ///
+    /// - `Some((t_enter, t_exit))`: The ray parameters at which the ray enters
+    ///                              and exits the box. `t_enter` is negative
+    ///                              if the ray starts inside the box.
+    /// - `None`:                    The ray misses the box, or the box lies
+    ///                              behind the ray.
+    pub fn intersection_ray(&self, ray: &Ray3<T>) -> Option<(T, T)> {
+        let (mn, mx) = (self.min(), self.max());
+        let mut t_enter = Float::neg_infinity::<T>();
+        let mut t_exit = Float::infinity::<T>();
+
+        let mut i = 0;
+        while i < 3 {
+            let (origin, dir) = (*ray.origin.i(i), *ray.direction.i(i));
+            if dir == zero!(T) {
+                // parallel to the slab, so the origin must lie within it
+                if origin < *mn.i(i) || origin > *mx.i(i) { return None; }
+            } else {
+                let t0 = (*mn.i(i) - origin) / dir;
+                let t1 = (*mx.i(i) - origin) / dir;
+                t_enter = t_enter.max(&t0.min(&t1));
+                t_exit = t_exit.min(&t0.max(&t1));
+                if t_enter > t_exit { return None; }
+            }
+            i += 1;
+        }
+
+        if t_exit < zero!(T) { None } else { Some((t_enter, t_exit)) }
+    }
+
+    /// Computes the point at which `ray` enters the box, or the point at which
+    /// it leaves the box if the ray starts inside it
+    pub fn intersection_point(&self, ray: &Ray3<T>) -> Option<Point3<T>> {
+        do self.intersection_ray(ray).map |&(ref t_enter, ref t_exit)| {
+            if *t_enter < zero!(T) {
+                ray.point_at(t_exit.clone())
+            } else {
+                ray.point_at(t_enter.clone())
+            }
+        }
+    }
+}
+
+/// An iterator over the points of a regular lattice within a box. See
+/// `AABB3::grid_points`.
+pub struct GridIterator<T> {
+    origin: Point3<T>,
+    step: Vec3<T>,
+    counts: [uint, ..3],
+    index: uint,
+}
+
+impl<T:Clone + Float> Iterator<Point3<T>> for GridIterator<T> {
+    pub fn next(&mut self) -> Option<Point3<T>> {
+        if self.index < self.counts[0] * self.counts[1] * self.counts[2] {
+            let x = self.index % self.counts[0];
+            let y = self.index / self.counts[0] % self.counts[1];
+            let z = self.index / (self.counts[0] * self.counts[1]);
+            self.index += 1;
+            Some(self.origin.translate_v(&self.step.mul_v(&Vec3::new(cast(x), cast(y), cast(z)))))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bounds::aabb::*;
+    use math::*;
+    use math::plane::{Front, Back, Intersecting};
+
+    #[test]
+    fn test_longest_axis() {
+        let a = AABB3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 3.0, 2.0));
+        let b = AABB3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0));
+        let c = AABB3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 4.0));
+
+        assert_eq!(a.longest_axis(), 1);
+        assert_eq!(b.longest_axis(), 0);
+        assert_eq!(c.longest_axis(), 2);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let a = AABB3::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(2.0, 4.0, 6.0));
+
+        assert_eq!(a.min(), Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(a.max(), Point3::new(2.0, 4.0, 6.0));
+    }
+
+    #[test]
+    fn test_merge_intersection() {
+        let a = AABB3::from_bounds(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+        let b = AABB3::from_bounds(Point3::new(1.0, 1.0, 1.0), Point3::new(3.0, 4.0, 2.0));
+        let touching = AABB3::from_bounds(Point3::new(2.0, 0.0, 0.0), Point3::new(3.0, 1.0, 1.0));
+        let disjoint = AABB3::from_bounds(Point3::new(5.0, 5.0, 5.0), Point3::new(6.0, 6.0, 6.0));
+
+        // overlapping
+        assert!(a.intersects(&b));
+        assert_eq!(a.intersection(&b),
+                   Some(AABB3::from_bounds(Point3::new(1.0, 1.0, 1.0), Point3::new(2.0, 2.0, 2.0))));
+        assert_eq!(a.merge(&b),
+                   AABB3::from_bounds(Point3::new(0.0, 0.0, 0.0), Point3::new(3.0, 4.0, 2.0)));
+
+        // touching
+        assert!(a.intersects(&touching));
+        assert_eq!(a.intersection(&touching),
+                   Some(AABB3::from_bounds(Point3::new(2.0, 0.0, 0.0), Point3::new(2.0, 1.0, 1.0))));
+
+        // disjoint
+        assert!(!a.intersects(&disjoint));
+        assert_eq!(a.intersection(&disjoint), None);
+        assert_eq!(a.merge(&disjoint),
+                   AABB3::from_bounds(Point3::new(0.0, 0.0, 0.0), Point3::new(6.0, 6.0, 6.0)));
+    }
+
+    #[test]
+    fn test_intersection_ray() {
+        let aabb = AABB3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0));
+        let outside = Ray3::new(Point3::new(-5.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let inside = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let miss = Ray3::new(Point3::new(-5.0, 3.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let behind = Ray3::new(Point3::new(5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        assert_eq!(aabb.intersection_ray(&outside), Some((4.0, 6.0)));
+        assert_eq!(aabb.intersection_ray(&inside), Some((-1.0, 1.0)));
+        assert_eq!(aabb.intersection_ray(&miss), None);
+        assert_eq!(aabb.intersection_ray(&behind), None);
+
+        assert_eq!(aabb.intersection_point(&outside), Some(Point3::new(-1.0, 0.5, 0.0)));
+        assert_eq!(aabb.intersection_point(&inside), Some(Point3::new(0.0, 1.0, 0.0)));
+        assert_eq!(aabb.intersection_point(&miss), None);
+    }
+
+    #[test]
+    fn test_closest_feature() {
+        let aabb = AABB3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0));
+        let corner = Point3::new(3.0, -2.0, 5.0);
+        let edge = Point3::new(3.0, 0.5, 5.0);
+        let face = Point3::new(0.5, -4.0, 0.0);
+        let inside = Point3::new(0.5, 0.5, -0.5);
+
+        assert_eq!(aabb.closest_point(&corner), Point3::new(1.0, -1.0, 1.0));
+        assert_eq!(aabb.closest_point(&edge), Point3::new(1.0, 0.5, 1.0));
+        assert_eq!(aabb.closest_point(&face), Point3::new(0.5, -1.0, 0.0));
+        assert_eq!(aabb.closest_point(&inside), inside);
+
+        assert_eq!(aabb.closest_feature(&corner), Vertex);
+        assert_eq!(aabb.closest_feature(&edge), Edge);
+        assert_eq!(aabb.closest_feature(&face), Face);
+        assert_eq!(aabb.closest_feature(&inside), Interior);
+    }
+
+    #[test]
+    fn test_grid_points() {
+        let aabb = AABB3::from_bounds(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 1.0, 1.0));
+
+        let points: ~[Point3<float>] = aabb.grid_points(&Vec3::new(1.0, 1.0, 1.0)).collect();
+        assert_eq!(points.len(), 12);
+        assert_eq!(points[0], Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(points[1], Point3::new(1.0, 0.0, 0.0));
+        assert_eq!(points[3], Point3::new(0.0, 1.0, 0.0));
+        assert_eq!(points[11], Point3::new(2.0, 1.0, 1.0));
+
+        let flat: ~[Point3<float>] = aabb.grid_points(&Vec3::new(1.0, 1.0, 0.0)).collect();
+        assert_eq!(flat.len(), 6);
+        assert!(flat.iter().all(|p| p.z == 0.0));
+
+        assert_eq!(aabb.grid_points(&Vec3::new(0.1, 1.0, 1.0)).count(), 21 * 2 * 2);
+    }
+
+    #[test]
+    fn test_classify_plane() {
+        let p = Plane3::from_abcd(0.0, 1.0, 0.0, -1.0);     // y = 1
+        let size = Vec3::new(2.0, 2.0, 2.0);
+
+        assert_eq!(AABB3::new(Point3::new(0.0, 2.5, 0.0), size).classify_plane(&p), Front);
+        assert_eq!(AABB3::new(Point3::new(0.0, -0.5, 0.0), size).classify_plane(&p), Back);
+        assert_eq!(AABB3::new(Point3::new(0.0, 1.5, 0.0), size).classify_plane(&p), Intersecting);
+
+        // the corner of the box pokes through a diagonal plane
+        let q = Plane3::from_abcd_normalized(1.0, 1.0, 0.0, -3.0);
+        assert_eq!(AABB3::new(Point3::new(1.0, 1.0, 0.0), size).classify_plane(&q), Intersecting);
+    }
+
+    #[test]
+    fn test_signed_distance_range() {
+        let p = Plane3::from_abcd(0.0, 1.0, 0.0, -1.0);     // y = 1
+        let aabb = AABB3::new(Point3::new(3.0, 1.5, -2.0), Vec3::new(2.0, 2.0, 2.0));
+        assert_eq!(aabb.signed_distance_range(&p), (-0.5, 1.5));
+
+        // the nearest and furthest corners of a box under a diagonal plane
+        let q = Plane3::from_abcd(0.6, 0.8, 0.0, 0.0);
+        let aabb = AABB3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0));
+        let (min, max) = aabb.signed_distance_range(&q);
+        assert_approx_eq!(min, q.distance(&Point3::new(-1.0, -1.0, 1.0)));
+        assert_approx_eq!(max, q.distance(&Point3::new(1.0, 1.0, -1.0)));
+    }
 }
@@ -15,12 +15,14 @@
 
 //! Bounding volumes
 
-pub use self::aabb::{AABB2, AABB3};
+pub use self::aabb::{AABB2, AABB3, Feature, GridIterator};
+pub use self::capsule::Capsule3;
 pub use self::frustum::{Frustum, FrustumPoints};
 pub use self::sphere::Sphere;
 
 pub mod aabb;
 pub mod box;
+pub mod capsule;
 pub mod cylinder;
 pub mod frustum;
 pub mod sphere;
@@ -0,0 +1,80 @@
+// Copyright 2013 The Lmath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounding capsule
+
+use math::*;
+
+/// A capsule, formed by sweeping a sphere of `radius` along `segment`
+#[deriving(Clone, Eq)]
+pub struct Capsule3<T> {
+    segment: Segment3<T>,
+    radius: T,
+}
+
+impl_approx!(Capsule3 { segment, radius })
+
+impl<T> Capsule3<T> {
+    #[inline]
+    pub fn new(segment: Segment3<T>, radius: T) -> Capsule3<T> {
+        Capsule3 { segment: segment, radius: radius }
+    }
+}
+
+impl<T:Clone + Float> Capsule3<T> {
+    /// Returns `true` if `point` lies inside or on the surface of the capsule
+    pub fn contains(&self, point: &Point3<T>) -> bool {
+        self.segment.closest_point(point).distance2(point) <= self.radius * self.radius
+    }
+
+    /// Returns `true` if the capsule overlaps or touches `other`
+    pub fn intersects_capsule(&self, other: &Capsule3<T>) -> bool {
+        let (a, b) = self.segment.closest_points(&other.segment);
+        let radii = self.radius + other.radius;
+        a.distance2(&b) <= radii * radii
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bounds::capsule::*;
+    use math::*;
+
+    #[test]
+    fn test_contains() {
+        let c = Capsule3::new(Segment3::new(Point3::new(0.0, 0.0, 0.0),
+                                            Point3::new(0.0, 2.0, 0.0)), 1.0);
+
+        assert!(c.contains(&Point3::new(0.9, 1.0, 0.0)));      // the cylindrical body
+        assert!(!c.contains(&Point3::new(1.1, 1.0, 0.0)));
+        assert!(c.contains(&Point3::new(0.0, 2.9, 0.0)));      // the top cap
+        assert!(c.contains(&Point3::new(0.0, -0.5, 0.5)));     // the bottom cap
+        assert!(!c.contains(&Point3::new(0.8, 2.8, 0.0)));     // outside the rounded corner
+    }
+
+    #[test]
+    fn test_intersects_capsule() {
+        let a = Capsule3::new(Segment3::new(Point3::new(0.0, 0.0, 0.0),
+                                            Point3::new(0.0, 2.0, 0.0)), 1.0);
+        let b = Capsule3::new(Segment3::new(Point3::new(1.5, 1.0, -1.0),
+                                            Point3::new(1.5, 1.0,  1.0)), 0.75);
+        let c = Capsule3::new(Segment3::new(Point3::new(0.0, 4.0, 0.0),
+                                            Point3::new(3.0, 4.0, 0.0)), 0.5);
+
+        assert!(a.intersects_capsule(&b));
+        assert!(b.intersects_capsule(&a));
+        assert!(!a.intersects_capsule(&c));
+    }
+}
@@ -102,3 +102,78 @@ impl<T:Clone + Float> Frustum<T> {
         }
     }
 }
+
+/// Computes the eight corners of the frustum bounded by `planes`, using the
+/// three-plane intersection of each corner's neighbouring planes.
+///
+/// The planes are expected in the order `[left, right, bottom, top, near,
+/// far]`, matching the fields of `Frustum`. The corners are returned in the
+/// order of the fields of `FrustumPoints`: the near top-left, top-right,
+/// bottom-left and bottom-right corners, followed by the same corners of the
+/// far plane.
+///
+/// # Failure
+///
+/// Fails if a corner cannot be computed because the normals of its planes are
+/// coplanar.
+pub fn frustum_corners<T:Clone + Float>(planes: &[Plane3<T>, ..6]) -> [Point3<T>, ..8] {
+    let points = Frustum::from_planes(planes[0].clone(), planes[1].clone(),
+                                      planes[2].clone(), planes[3].clone(),
+                                      planes[4].clone(), planes[5].clone()).to_points();
+    [points.near_top_left,
+     points.near_top_right,
+     points.near_bottom_left,
+     points.near_bottom_right,
+     points.far_top_left,
+     points.far_top_right,
+     points.far_bottom_left,
+     points.far_bottom_right]
+}
+
+#[cfg(test)]
+mod tests {
+    use bounds::frustum::*;
+    use math::*;
+
+    #[test]
+    fn test_frustum_corners() {
+        // an orthographic frustum spanning `-1 <= x <= 1`, `-2 <= y <= 2` and
+        // `-10 <= z <= -1`, with the normals of the planes facing inwards
+        let planes = [Plane3::from_abcd( 1.0,  0.0,  0.0,  1.0),     // left: x = -1
+                      Plane3::from_abcd(-1.0,  0.0,  0.0,  1.0),     // right: x = 1
+                      Plane3::from_abcd( 0.0,  1.0,  0.0,  2.0),     // bottom: y = -2
+                      Plane3::from_abcd( 0.0, -1.0,  0.0,  2.0),     // top: y = 2
+                      Plane3::from_abcd( 0.0,  0.0, -1.0, -1.0),     // near: z = -1
+                      Plane3::from_abcd( 0.0,  0.0,  1.0, 10.0)];    // far: z = -10
+        let corners = frustum_corners(&planes);
+
+        assert_eq!(corners[0], Point3::new(-1.0,  2.0,  -1.0));
+        assert_eq!(corners[1], Point3::new( 1.0,  2.0,  -1.0));
+        assert_eq!(corners[2], Point3::new(-1.0, -2.0,  -1.0));
+        assert_eq!(corners[3], Point3::new( 1.0, -2.0,  -1.0));
+        assert_eq!(corners[4], Point3::new(-1.0,  2.0, -10.0));
+        assert_eq!(corners[5], Point3::new( 1.0,  2.0, -10.0));
+        assert_eq!(corners[6], Point3::new(-1.0, -2.0, -10.0));
+        assert_eq!(corners[7], Point3::new( 1.0, -2.0, -10.0));
+    }
+
+    #[test]
+    fn test_from_matrix_to_points() {
+        // an orthographic projection of `-1 <= x <= 1`, `-2 <= y <= 2` and
+        // `-3 <= z <= -1`
+        let proj = Mat4::new::<float>(1.0, 0.0,  0.0, 0.0,
+                                      0.0, 0.5,  0.0, 0.0,
+                                      0.0, 0.0, -1.0, 0.0,
+                                      0.0, 0.0, -2.0, 1.0);
+        let points = Frustum::from_matrix(proj).to_points();
+
+        assert_approx_eq!(points.near_top_left,     Point3::new(-1.0,  2.0, -1.0));
+        assert_approx_eq!(points.near_top_right,    Point3::new( 1.0,  2.0, -1.0));
+        assert_approx_eq!(points.near_bottom_left,  Point3::new(-1.0, -2.0, -1.0));
+        assert_approx_eq!(points.near_bottom_right, Point3::new( 1.0, -2.0, -1.0));
+        assert_approx_eq!(points.far_top_left,      Point3::new(-1.0,  2.0, -3.0));
+        assert_approx_eq!(points.far_top_right,     Point3::new( 1.0,  2.0, -3.0));
+        assert_approx_eq!(points.far_bottom_left,   Point3::new(-1.0, -2.0, -3.0));
+        assert_approx_eq!(points.far_bottom_right,  Point3::new( 1.0, -2.0, -3.0));
+    }
+}
@@ -23,6 +23,8 @@
 #[license = "ASL2"];
 #[crate_type = "lib"];
 
+extern mod extra;
+
 // Macros
 
 mod macros;
@@ -33,6 +33,55 @@ macro_rules! two(
     ($T:ty) => (one!(T) + one!(T));
 )
 
+macro_rules! impl_approx_rel(
+    ($T:ident { $($field:ident),+ }) => (
+        impl<T:Clone + Orderable + Mul<T,T> + ::math::ApproxEqRel<T>> ::math::ApproxEqRel<T> for $T<T> {
+            #[inline]
+            pub fn approx_eq_rel(&self, other: &$T<T>, max_relative: &T) -> bool {
+                use math::ApproxEqRel;
+                let largest = self.max_abs().max(&other.max_abs());
+                self.approx_eq_abs(other, &(largest * *max_relative))
+            }
+
+            #[inline]
+            pub fn max_abs(&self) -> T {
+                use math::ApproxEqRel;
+                let fields = [$( self.$field.max_abs() ),+];
+                let mut largest = fields[0].clone();
+                for fields.iter().advance |x| { largest = largest.max(x); }
+                largest
+            }
+
+            #[inline]
+            pub fn approx_eq_abs(&self, other: &$T<T>, tolerance: &T) -> bool {
+                use math::ApproxEqRel;
+                $( self.$field.approx_eq_abs(&other.$field, tolerance) )&&+
+            }
+        }
+    );
+    ($T:ident) => (
+        impl<T: ::math::ApproxEqRel<T>> ::math::ApproxEqRel<T> for $T<T> {
+            #[inline]
+            pub fn approx_eq_rel(&self, other: &$T<T>, max_relative: &T) -> bool {
+                use math::ApproxEqRel;
+                (**self).approx_eq_rel(&**other, max_relative)
+            }
+
+            #[inline]
+            pub fn max_abs(&self) -> T {
+                use math::ApproxEqRel;
+                (**self).max_abs()
+            }
+
+            #[inline]
+            pub fn approx_eq_abs(&self, other: &$T<T>, tolerance: &T) -> bool {
+                use math::ApproxEqRel;
+                (**self).approx_eq_abs(&**other, tolerance)
+            }
+        }
+    )
+)
+
 macro_rules! impl_approx(
     ($T:ident { $($field:ident),+ }) => (
         impl<T:Clone + Eq + ApproxEq<T>> ApproxEq<T> for $T<T> {
@@ -51,6 +100,29 @@ macro_rules! impl_approx(
                 $( self.$field.approx_eq_eps(&other.$field, epsilon) )&&+
             }
         }
+
+        impl_approx_rel!($T { $($field),+ })
+    );
+    ($T:ident { $($field:ident),+ } eps = $eps:expr) => (
+        impl<T:Clone + Eq + ApproxEq<T> + NumCast> ApproxEq<T> for $T<T> {
+            #[inline]
+            pub fn approx_epsilon() -> T {
+                use std::num::cast;
+                cast($eps)
+            }
+
+            #[inline]
+            pub fn approx_eq(&self, other: &$T<T>) -> bool {
+                self.approx_eq_eps(other, &ApproxEq::approx_epsilon::<T,$T<T>>())
+            }
+
+            #[inline]
+            pub fn approx_eq_eps(&self, other: &$T<T>, epsilon: &T) -> bool {
+                $( self.$field.approx_eq_eps(&other.$field, epsilon) )&&+
+            }
+        }
+
+        impl_approx_rel!($T { $($field),+ })
     );
     ($T:ident) => (
         impl<T:Clone + Eq + ApproxEq<T>> ApproxEq<T> for $T<T> {
@@ -69,5 +141,7 @@ macro_rules! impl_approx(
                 (**self).approx_eq_eps(&**other, epsilon)
             }
         }
+
+        impl_approx_rel!($T)
     )
 )
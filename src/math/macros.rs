@@ -131,3 +131,34 @@ macro_rules! impl_swap_components(
         }
     )
 )
+
+macro_rules! impl_bit_eq(
+    ($Self:ident { $($field:ident),+ }) => (
+        impl<T:BitEq> BitEq for $Self<T> {
+            #[inline]
+            pub fn bit_eq(&self, other: &$Self<T>) -> bool {
+                $( self.$field.bit_eq(&other.$field) )&&+
+            }
+        }
+    )
+)
+
+macro_rules! impl_approx_eq_rel(
+    ($Self:ident) => (
+        impl ApproxEqRel<$Self> for $Self {
+            #[inline]
+            pub fn approx_eq_rel(&self, other: &$Self, max_relative: &$Self) -> bool {
+                let largest = self.max_abs().max(&other.max_abs());
+                self.approx_eq_abs(other, &(largest * *max_relative))
+            }
+
+            #[inline]
+            pub fn max_abs(&self) -> $Self { self.abs() }
+
+            #[inline]
+            pub fn approx_eq_abs(&self, other: &$Self, tolerance: &$Self) -> bool {
+                (*self - *other).abs() <= *tolerance
+            }
+        }
+    )
+)
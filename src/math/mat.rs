@@ -769,6 +769,48 @@ impl<T:Float> Mat3<T> {
 
         Mat3::from_axes(up_, side, dir_)
     }
+
+    /// Returns `true` if the matrix is a rotation to within `epsilon`, that is,
+    /// if its columns are of unit length and mutually orthogonal, and its
+    /// determinant is `1`. This should be checked before converting a matrix
+    /// of unknown origin to a quaternion.
+    pub fn is_rotation(&self, epsilon: &T) -> bool {
+        self.x.magnitude2().approx_eq_eps(&one!(T), epsilon) &&
+        self.y.magnitude2().approx_eq_eps(&one!(T), epsilon) &&
+        self.z.magnitude2().approx_eq_eps(&one!(T), epsilon) &&
+        self.x.dot(&self.y).approx_eq_eps(&zero!(T), epsilon) &&
+        self.y.dot(&self.z).approx_eq_eps(&zero!(T), epsilon) &&
+        self.z.dot(&self.x).approx_eq_eps(&zero!(T), epsilon) &&
+        self.determinant().approx_eq_eps(&one!(T), epsilon)
+    }
+
+    /// Constructs a rotation whose local `z` axis points along `dir`, as used
+    /// in a left-handed coordinate system where the viewer looks down the
+    /// positive `z` axis.
+    ///
+    /// The columns of the resulting matrix are the local `x` (right), `y`
+    /// (up) and `z` (forward) axes expressed in world space.
+    pub fn look_at_lh(dir: &Vec3<T>, up: &Vec3<T>) -> Mat3<T> {
+        let z = dir.normalize();
+        let x = up.cross(&z).normalize();
+        let y = z.cross(&x);
+
+        Mat3::from_axes(x, y, z)
+    }
+
+    /// Constructs a rotation whose local `z` axis points away from `dir`, as
+    /// used in a right-handed coordinate system where the viewer looks down
+    /// the negative `z` axis.
+    ///
+    /// The columns of the resulting matrix are the local `x` (right), `y`
+    /// (up) and `z` (backward) axes expressed in world space.
+    pub fn look_at_rh(dir: &Vec3<T>, up: &Vec3<T>) -> Mat3<T> {
+        let z = -dir.normalize();
+        let x = up.cross(&z).normalize();
+        let y = z.cross(&x);
+
+        Mat3::from_axes(x, y, z)
+    }
 }
 
 impl<T:Clone + Float> ToQuat<T> for Mat3<T> {
@@ -795,28 +837,28 @@ impl<T:Clone + Float> ToQuat<T> for Mat3<T> {
             }
             ((*self.cr(0, 0) > *self.cr(1, 1))
             && (*self.cr(0, 0) > *self.cr(2, 2))) {
-                s = (half + (*self.cr(0, 0) - *self.cr(1, 1) - *self.cr(2, 2))).sqrt();
-                w = half * s;
+                s = (one!(T) + *self.cr(0, 0) - *self.cr(1, 1) - *self.cr(2, 2)).sqrt();
+                x = half * s;
                 s = half / s;
-                x = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
-                y = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
-                z = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
+                w = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
+                y = (*self.cr(1, 0) + *self.cr(0, 1)) * s;
+                z = (*self.cr(2, 0) + *self.cr(0, 2)) * s;
             }
             (*self.cr(1, 1) > *self.cr(2, 2)) {
-                s = (half + (*self.cr(1, 1) - *self.cr(0, 0) - *self.cr(2, 2))).sqrt();
-                w = half * s;
+                s = (one!(T) + *self.cr(1, 1) - *self.cr(0, 0) - *self.cr(2, 2)).sqrt();
+                y = half * s;
                 s = half / s;
-                x = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
-                y = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
-                z = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
+                w = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
+                x = (*self.cr(1, 0) + *self.cr(0, 1)) * s;
+                z = (*self.cr(2, 1) + *self.cr(1, 2)) * s;
             }
             _ {
-                s = (half + (*self.cr(2, 2) - *self.cr(0, 0) - *self.cr(1, 1))).sqrt();
-                w = half * s;
+                s = (one!(T) + *self.cr(2, 2) - *self.cr(0, 0) - *self.cr(1, 1)).sqrt();
+                z = half * s;
                 s = half / s;
-                x = (*self.cr(2, 0) - *self.cr(0, 2)) * s;
-                y = (*self.cr(1, 2) - *self.cr(2, 1)) * s;
-                z = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
+                w = (*self.cr(0, 1) - *self.cr(1, 0)) * s;
+                x = (*self.cr(2, 0) + *self.cr(0, 2)) * s;
+                y = (*self.cr(2, 1) + *self.cr(1, 2)) * s;
             }
         )
         Quat::new(w, x, y, z)
@@ -1091,6 +1133,40 @@ mod mat3_tests{
                                    0.0000001, 0.0000001, 0.0000001)
                 .approx_eq(&Mat3::zero::<float>()));
     }
+
+    #[test]
+    fn test_look_at() {
+        let dir = Vec3::new::<float>(2.0, 0.0, 0.0);
+        let up = Vec3::new::<float>(0.0, 1.0, 0.0);
+
+        let lh = Mat3::look_at_lh(&dir, &up);
+        assert_eq!(lh, Mat3::from_axes(Vec3::new(0.0, 0.0, -1.0),
+                                       Vec3::new(0.0, 1.0,  0.0),
+                                       Vec3::new(1.0, 0.0,  0.0)));
+        assert_eq!(lh.x.cross(&lh.y), lh.z);
+
+        let rh = Mat3::look_at_rh(&dir, &up);
+        assert_eq!(rh, Mat3::from_axes(Vec3::new( 0.0, 0.0, 1.0),
+                                       Vec3::new( 0.0, 1.0, 0.0),
+                                       Vec3::new(-1.0, 0.0, 0.0)));
+        assert_eq!(rh.x.cross(&rh.y), rh.z);
+    }
+
+    #[test]
+    fn test_is_rotation() {
+        let eps = 1.0e-6;
+
+        assert!(Mat3::identity::<float>().is_rotation(&eps));
+        assert!(Mat3::look_at_lh(&Vec3::new::<float>(1.0, 2.0, 3.0),
+                                 &Vec3::new::<float>(0.0, 1.0, 0.0)).is_rotation(&eps));
+        assert!(!Mat3::identity::<float>().mul_s(2.0).is_rotation(&eps));
+        assert!(!Mat3::new::<float>(1.0, 0.0, 0.0,
+                                    0.0, 1.0, 0.0,
+                                    0.0, 0.0, -1.0).is_rotation(&eps));
+        assert!(!Mat3::new::<float>(1.0, 0.0, 0.0,
+                                    0.5, 1.0, 0.0,
+                                    0.0, 0.0, 1.0).is_rotation(&eps));
+    }
 }
 
 #[deriving(Clone, Eq)]
@@ -26,11 +26,13 @@ pub use self::vec::{Vec2, ToVec2, AsVec2};
 pub use self::vec::{Vec3, ToVec3, AsVec3};
 pub use self::vec::{Vec4, ToVec4, AsVec4};
 
-pub use self::plane::Plane3;
+pub use self::plane::{Plane3, PlaneSide};
 pub use self::point::Point;
 pub use self::point::{Point2, AsPoint2};
 pub use self::point::{Point3, AsPoint3};
 pub use self::ray::{Ray2, Ray3};
+pub use self::segment::{Segment2, Segment3};
+pub use self::triangle::Triangle3;
 
 pub mod mat;
 pub mod quat;
@@ -39,6 +41,8 @@ pub mod vec;
 pub mod plane;
 pub mod point;
 pub mod ray;
+pub mod segment;
+pub mod triangle;
 
 pub trait Dimensioned<T,Slice> {
     fn i<'a>(&'a self, i: uint) -> &'a T;
@@ -51,6 +55,65 @@ pub trait SwapComponents {
     fn swap(&mut self, a: uint, b: uint);
 }
 
+/// Exact comparison of the underlying bit patterns
+///
+/// Unlike `==`, this distinguishes `0.0` from `-0.0` and considers a `NaN`
+/// equal to another `NaN` with the same bits. Unlike `approx_eq`, no
+/// tolerance is allowed. This makes it suitable for deterministic
+/// deduplication and for use as a map key.
+pub trait BitEq {
+    fn bit_eq(&self, other: &Self) -> bool;
+}
+
+impl BitEq for f32 {
+    #[inline]
+    pub fn bit_eq(&self, other: &f32) -> bool {
+        use std::cast::transmute;
+        unsafe { transmute::<f32, u32>(*self) == transmute::<f32, u32>(*other) }
+    }
+}
+
+impl BitEq for f64 {
+    #[inline]
+    pub fn bit_eq(&self, other: &f64) -> bool {
+        use std::cast::transmute;
+        unsafe { transmute::<f64, u64>(*self) == transmute::<f64, u64>(*other) }
+    }
+}
+
+impl BitEq for float {
+    #[inline]
+    pub fn bit_eq(&self, other: &float) -> bool {
+        (*self as f64).bit_eq(&(*other as f64))
+    }
+}
+
+/// Approximate comparison using a tolerance relative to the magnitude of the
+/// values being compared
+///
+/// `approx_eq_eps` compares against an absolute epsilon, which becomes
+/// meaningless for values far from zero, where neighbouring floats can be
+/// further apart than the epsilon. Here the allowed difference is
+/// `max_relative` times the largest component magnitude of either value, so
+/// that a component near zero is measured against the scale of the whole
+/// value rather than against its own magnitude.
+pub trait ApproxEqRel<Eps> {
+    fn approx_eq_rel(&self, other: &Self, max_relative: &Eps) -> bool;
+
+    /// The largest magnitude of any of the components
+    fn max_abs(&self) -> Eps;
+
+    /// Returns `true` if every component differs from the corresponding
+    /// component of `other` by at most `tolerance`
+    fn approx_eq_abs(&self, other: &Self, tolerance: &Eps) -> bool;
+}
+
+// `ApproxEqRel` impls for primitive float types
+
+impl_approx_eq_rel!(f32)
+impl_approx_eq_rel!(f64)
+impl_approx_eq_rel!(float)
+
 // `Dimensioned` impls for primitive numeric types
 
 impl_dimensioned!(u8)
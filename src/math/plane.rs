@@ -15,9 +15,9 @@
 
 //! Three-dimensional plane type
 
-use math::{Vec3, Vec4, Mat3};
+use math::{Vec3, Vec4, ToVec4, Mat3, Mat4};
 use math::{Point, Point3};
-use math::Ray3;
+use math::{Ray2, Ray3};
 
 /// A plane formed from the equation: `Ax + Bx + Cx + D = 0`
 ///
@@ -36,6 +36,17 @@ pub struct Plane3<T> {
 
 impl_approx!(Plane3 { normal, distance })
 
+/// The side of a plane on which a volume lies
+#[deriving(Clone, Eq)]
+pub enum PlaneSide {
+    /// Entirely in front of the plane, on the side the normal points to
+    Front,
+    /// Entirely behind the plane
+    Back,
+    /// Straddling the plane
+    Intersecting,
+}
+
 impl<T:Clone + Float> Plane3<T> {
     /// # Arguments
     ///
@@ -50,6 +61,21 @@ impl<T:Clone + Float> Plane3<T> {
         }
     }
 
+    /// Constructs a plane from the coefficients of the plane equation, scaled
+    /// so that the normal is of unit length. This ensures that `distance`
+    /// returns the true geometric distance.
+    ///
+    /// # Arguments
+    ///
+    /// - `a`: the `x` component of the normal
+    /// - `b`: the `y` component of the normal
+    /// - `c`: the `z` component of the normal
+    /// - `d`: the plane's distance value
+    #[inline]
+    pub fn from_abcd_normalized(a: T, b: T, c: T, d: T) -> Plane3<T> {
+        Plane3::from_abcd(a, b, c, d).normalize()
+    }
+
     /// Construct a plane from a normal vector and a scalar distance
     pub fn from_nd(normal: Vec3<T>, distance: T) -> Plane3<T> {
         Plane3 { normal: normal, distance: distance }
@@ -60,25 +86,215 @@ impl<T:Clone + Float> Plane3<T> {
         Plane3::from_abcd(vec.x.clone(), vec.y.clone(), vec.z.clone(), vec.w.clone())
     }
 
+    /// Constructs a parallel plane, moved along the normal by `delta`.
+    ///
+    /// This assumes that the normal is of unit length. Otherwise the plane is
+    /// moved by `delta` divided by the magnitude of the normal.
+    #[inline]
+    pub fn offset(&self, delta: T) -> Plane3<T> {
+        Plane3::from_nd(self.normal.clone(), self.distance - delta)
+    }
+
+    /// Constructs a plane with the same normal but with the distance value
+    /// replaced by `distance`
+    #[inline]
+    pub fn with_distance(&self, distance: T) -> Plane3<T> {
+        Plane3::from_nd(self.normal.clone(), distance)
+    }
+
+    /// Scales the plane equation so that the normal is of unit length. This
+    /// leaves the plane itself unchanged.
+    #[inline]
+    pub fn normalize(&self) -> Plane3<T> {
+        let rlen = self.normal.magnitude().recip();
+        Plane3::from_nd(self.normal.mul_s(rlen.clone()), self.distance * rlen)
+    }
+
+    /// Reverses the direction the plane faces, swapping its front and back
+    /// sides.
+    #[inline]
+    pub fn flip(&self) -> Plane3<T> {
+        Plane3::from_nd(-self.normal, -self.distance)
+    }
+
+    /// Computes a unique representation of the plane, independent of the
+    /// scale of the plane equation and of the side the normal faces.
+    ///
+    /// The plane is normalized, then flipped if needed so that the first
+    /// nonzero component of its normal is positive.
+    pub fn canonicalize(&self) -> Plane3<T> {
+        let p = self.normalize();
+        let first = if p.normal.x != zero!(T) {
+            p.normal.x.clone()
+        } else if p.normal.y != zero!(T) {
+            p.normal.y.clone()
+        } else {
+            p.normal.z.clone()
+        };
+        if first < zero!(T) { p.flip() } else { p }
+    }
+
     /// Compute the distance from the plane to the point
     pub fn distance(&self, pos: &Point3<T>) -> T {
         self.normal.dot(pos.as_vec3()) + self.distance
     }
 
+    /// Computes the intersection of `ray` with the plane
+    ///
+    /// # Return value
+    ///
+    /// - `Some((t, p))`: The ray parameter `t` and the point `p` where the
+    ///                   ray hits the plane, such that `p == ray.point_at(t)`.
+    /// - `None`:         The ray is parallel to the plane, or points away
+    ///                   from it.
+    pub fn intersection_ray_t(&self, ray: &Ray3<T>) -> Option<(T, Point3<T>)> {
+        let denom = self.normal.dot(&ray.direction);
+        if denom.approx_eq(&zero!(T)) {
+            None    // the ray is parallel to the plane
+        } else {
+            let t = -self.distance(&ray.origin) / denom;
+            if t < zero!(T) {
+                None
+            } else {
+                Some((t.clone(), ray.point_at(t)))
+            }
+        }
+    }
+
+    /// Returns two orthonormal tangent vectors that span the plane, as
+    /// constructed by `Vec3::build_basis`. The normal of the plane should be
+    /// of unit length.
+    #[inline]
+    pub fn basis(&self) -> (Vec3<T>, Vec3<T>) {
+        self.normal.build_basis()
+    }
+
+    /// Maps the 2D coordinates `(u, v)` onto the plane, along the tangent
+    /// vectors returned by `basis`, with `(0, 0)` at the point on the plane
+    /// closest to the origin. The normal of the plane should be of unit
+    /// length.
+    pub fn point_on_plane(&self, u: T, v: T) -> Point3<T> {
+        let (tangent, bitangent) = self.basis();
+        Point3::from_vec3(self.normal.mul_s(-self.distance)
+                                     .add_v(&tangent.mul_s(u))
+                                     .add_v(&bitangent.mul_s(v)))
+    }
+
+    /// Removes the component of the direction `v` along the normal, leaving
+    /// the part that is tangent to the plane. The normal of the plane should
+    /// be of unit length.
+    #[inline]
+    pub fn project_vector(&self, v: &Vec3<T>) -> Vec3<T> {
+        v.sub_v(&self.normal.mul_s(v.dot(&self.normal)))
+    }
+
+    /// Mirrors `pos` across the plane. The normal of the plane should be of
+    /// unit length.
+    #[inline]
+    pub fn reflect_point(&self, pos: &Point3<T>) -> Point3<T> {
+        *pos + self.normal.mul_s(-two!(T) * self.distance(pos))
+    }
+
+    /// Constructs the homogeneous matrix that mirrors points across the
+    /// plane, for example to render planar reflections. The normal of the
+    /// plane should be of unit length.
+    pub fn reflection_matrix(&self) -> Mat4<T> {
+        let n = &self.normal;
+        let _2: T = two!(T);
+        let _1: T = one!(T);
+        let _0: T = zero!(T);
+
+        Mat4::new(_1 - _2 * n.x * n.x, -_2 * n.x * n.y, -_2 * n.x * n.z, _0.clone(),
+                  -_2 * n.y * n.x, _1 - _2 * n.y * n.y, -_2 * n.y * n.z, _0.clone(),
+                  -_2 * n.z * n.x, -_2 * n.z * n.y, _1 - _2 * n.z * n.z, _0,
+                  -_2 * self.distance * n.x, -_2 * self.distance * n.y, -_2 * self.distance * n.z, _1.clone())
+    }
+
+    /// Computes the point at which the infinite line passing through `a` and
+    /// `b` crosses the plane. Unlike `intersection_r`, the crossing may lie on
+    /// either side of `a`.
+    ///
+    /// # Return value
+    ///
+    /// - `Some(p)`: The point `p` where the line crosses the plane.
+    /// - `None`:    The line is parallel to the plane, or `a` and `b`
+    ///              coincide.
+    pub fn intersection_line(&self, a: &Point3<T>, b: &Point3<T>) -> Option<Point3<T>> {
+        let dir = *b - *a;
+        let denom = self.normal.dot(&dir);
+        if denom.approx_eq(&zero!(T)) {
+            None
+        } else {
+            Some(*a + dir.mul_s(-self.distance(a) / denom))
+        }
+    }
+
+    /// Computes where the segment from `a` to `b` crosses the plane, as the
+    /// interpolation parameter `t` between the end points. The crossing point
+    /// is `a + (b - a) * t`, and the same `t` can be used to interpolate any
+    /// attributes stored at the end points.
+    ///
+    /// # Return value
+    ///
+    /// - `Some(t)`: The parameter in `[0, 1]` where the segment crosses the
+    ///              plane.
+    /// - `None`:    The segment does not reach the plane, or is parallel
+    ///              to it.
+    pub fn intersection_segment_t(&self, a: &Point3<T>, b: &Point3<T>) -> Option<T> {
+        let dist_a = self.distance(a);
+        let denom = dist_a - self.distance(b);
+        if denom.approx_eq(&zero!(T)) {
+            None
+        } else {
+            let t = dist_a / denom;
+            if t < zero!(T) || t > one!(T) { None } else { Some(t) }
+        }
+    }
+
+    /// Returns `true` if the end points of the segment from `a` to `b` lie on
+    /// opposite sides of the plane, or if either of them lies on it.
+    #[inline]
+    pub fn intersects_segment(&self, a: &Point3<T>, b: &Point3<T>) -> bool {
+        self.distance(a) * self.distance(b) <= zero!(T)
+    }
+
     /// Computes the point at which `ray` intersects the plane
-    pub fn intersection_r(&self, _ray: &Ray3<T>) -> Point3<T> {
-        fail!(~"not yet implemented")
+    ///
+    /// # Return value
+    ///
+    /// - `Some(p)`: The point `p` where the ray hits the plane.
+    /// - `None`:    The ray is parallel to the plane, or points away from it.
+    #[inline]
+    pub fn intersection_r(&self, ray: &Ray3<T>) -> Option<Point3<T>> {
+        do self.intersection_ray_t(ray).map |&(_, ref p)| { p.clone() }
     }
 
     /// Returns `true` if the ray intersects the plane
-    pub fn intersects(&self, _ray: &Ray3<T>) -> bool {
-        fail!(~"not yet implemented")
+    #[inline]
+    pub fn intersects(&self, ray: &Ray3<T>) -> bool {
+        self.intersection_ray_t(ray).is_some()
     }
 
     /// Returns `true` if `pos` is located behind the plane - otherwise it returns `false`
     pub fn contains(&self, pos: &Point3<T>) -> bool {
         self.distance(pos) < zero!(T)
     }
+
+    /// Determines which side of the plane the sphere with the given `center`
+    /// and `radius` lies on. The normal of the plane should be of unit length.
+    ///
+    /// Boxes are classified by `AABB3::classify_plane` instead, as the `math`
+    /// module cannot refer to the types in `bounds`.
+    pub fn classify_sphere(&self, center: &Point3<T>, radius: T) -> PlaneSide {
+        let dist = self.distance(center);
+        if dist > radius {
+            Front
+        } else if dist < -radius {
+            Back
+        } else {
+            Intersecting
+        }
+    }
 }
 
 impl<T:Clone + Float> Plane3<T> {
@@ -103,6 +319,99 @@ impl<T:Clone + Float> Plane3<T> {
         }
     }
 
+    /// Constructs the plane swept out by extruding a 2D line along `up`.
+    ///
+    /// The line is placed in the `xz` plane, with the `x` and `y` coordinates
+    /// of `ray` mapped to `x` and `z`. The normal of the plane is the cross
+    /// product of the direction of the line with `up`, so `up` must not be
+    /// parallel to the line.
+    pub fn from_ray2_extruded(ray: &Ray2<T>, up: &Vec3<T>) -> Plane3<T> {
+        let origin = Vec3::new(ray.origin.x.clone(), zero!(T), ray.origin.y.clone());
+        let dir = Vec3::new(ray.direction.x.clone(), zero!(T), ray.direction.y.clone());
+        let normal = dir.cross(up).normalize();
+        let distance = -origin.dot(&normal);
+
+        Plane3::from_nd(normal, distance)
+    }
+
+    /// Constructs the plane of a polygon, using Newell's method to compute
+    /// the normal from all of the vertices and passing the plane through their
+    /// centroid.
+    ///
+    /// This is less sensitive than `from_3p` to any three of the vertices
+    /// being nearly collinear. The vertices should be supplied in order
+    /// around the polygon, and the normal points towards the side from which
+    /// they appear counter-clockwise.
+    ///
+    /// # Return value
+    ///
+    /// - `Some(p)`: The plane `p` of the polygon.
+    /// - `None`:    Fewer than three points were supplied, or the polygon has
+    ///              no area.
+    pub fn from_coplanar_points(points: &[Point3<T>]) -> Option<Plane3<T>> {
+        if points.len() < 3 { return None; }
+
+        let normal = newell_normal(points);
+        if normal.approx_eq(&Vec3::zero()) { return None; }
+        let normal = normal.normalize();
+
+        let centroid = centroid(points);
+
+        Some(Plane3::from_nd(normal.clone(), -centroid.dot(&normal)))
+    }
+
+    /// Computes the plane that best fits `points` in the least-squares sense.
+    ///
+    /// # Return value
+    ///
+    /// - `Some(p)`: The best fitting plane `p`, which passes through the
+    ///              centroid of the points.
+    /// - `None`:    Fewer than three points were supplied, or the points are
+    ///              collinear.
+    pub fn fit(points: &[Point3<T>]) -> Option<Plane3<T>> {
+        if points.len() < 3 { return None; }
+
+        let centroid = centroid(points);
+
+        // accumulate the covariance matrix of the points about the centroid
+        let mut xx = zero!(T); let mut xy = zero!(T); let mut xz = zero!(T);
+        let mut yy = zero!(T); let mut yz = zero!(T); let mut zz = zero!(T);
+        for points.iter().advance |p| {
+            let r = p.as_vec3().sub_v(&centroid);
+            xx = xx + r.x * r.x;
+            xy = xy + r.x * r.y;
+            xz = xz + r.x * r.z;
+            yy = yy + r.y * r.y;
+            yz = yz + r.y * r.z;
+            zz = zz + r.z * r.z;
+        }
+
+        let det_x = yy * zz - yz * yz;
+        let det_y = xx * zz - xz * xz;
+        let det_z = xx * yy - xy * xy;
+        let det_max = det_x.max(&det_y).max(&det_z);
+
+        // the determinants vanish relative to the spread of the points when
+        // they all lie on a line
+        let trace = xx + yy + zz;
+        if det_max <= trace * trace * ApproxEq::approx_epsilon::<T,T>() {
+            None
+        } else {
+            // solve for the normal along the best conditioned axis
+            let mut normal = if det_max == det_x {
+                Vec3::new(det_x, xz * yz - xy * zz, xy * yz - xz * yy)
+            } else if det_max == det_y {
+                Vec3::new(xz * yz - xy * zz, det_y, xy * xz - yz * xx)
+            } else {
+                Vec3::new(xy * yz - xz * yy, xy * xz - yz * xx, det_z)
+            };
+            normal.normalize_self();
+            let distance = -centroid.dot(&normal);
+
+            Some(Plane3::from_nd(normal, distance))
+        }
+    }
+
     /// Computes the ray created from the two-plane intersection of `self` and `other`
     ///
     /// # Return value
@@ -130,19 +439,76 @@ impl<T:Clone + Float> Plane3<T> {
     ///
     /// # Return value
     ///
-    /// - `Some(p)`: The position vector `p` where the planes intersect.
+    /// - `Some(p)`: The position vector `p` where the planes intersect, such
+    ///              that the `distance` from each plane to `p` is zero.
     /// - `None`:    No valid intersection was found. The normals of the three
     ///              planes are probably coplanar.
     pub fn intersection_3pl(&self, other_a: &Plane3<T>, other_b: &Plane3<T>) -> Option<Point3<T>> {
         let mx = Mat3::new(self.normal.x.clone(), other_a.normal.x.clone(), other_b.normal.x.clone(),
                            self.normal.y.clone(), other_a.normal.y.clone(), other_b.normal.y.clone(),
                            self.normal.z.clone(), other_a.normal.z.clone(), other_b.normal.z.clone());
+        // solve `n . p = -d` for each of the planes
         do mx.inverse().map |m| {
-            Point3::origin() + m.mul_v(&Vec3::new(self.distance.clone(),
-                                                  other_a.distance.clone(),
-                                                  other_b.distance.clone()))
+            Point3::origin() + m.mul_v(&Vec3::new(-self.distance,
+                                                  -other_a.distance,
+                                                  -other_b.distance))
         }
     }
+
+    /// Computes the three-plane intersection between `self`, `other_a` and
+    /// `other_b` in homogeneous coordinates, without performing the division
+    /// by the determinant of the normals.
+    ///
+    /// # Return value
+    ///
+    /// The homogeneous point `[x, y, z, w]`. Dividing `x`, `y` and `z` by `w`
+    /// gives the point where the planes meet, at which the `distance` from
+    /// each plane is zero. A `w` close to zero means that the planes are
+    /// nearly parallel and meet at infinity.
+    pub fn intersection_3pl_homogeneous(&self, other_a: &Plane3<T>, other_b: &Plane3<T>) -> Vec4<T> {
+        let ab = other_a.normal.cross(&other_b.normal);
+        let bc = other_b.normal.cross(&self.normal);
+        let ca = self.normal.cross(&other_a.normal);
+        // the planes satisfy `n . p = -d`
+        let xyz = -ab.mul_s(self.distance.clone())
+                     .add_v(&bc.mul_s(other_a.distance.clone()))
+                     .add_v(&ca.mul_s(other_b.distance.clone()));
+        Vec4::new(xyz.x.clone(), xyz.y.clone(), xyz.z.clone(), self.normal.dot(&ab))
+    }
+}
+
+/// Computes the normal of a polygon using Newell's method, which remains
+/// robust for concave and slightly non-planar polygons.
+///
+/// The vertices should be supplied in order around the polygon. The normal
+/// points towards the side from which the vertices appear counter-clockwise.
+///
+/// # Return value
+///
+/// The unnormalized normal vector. For planar polygons its magnitude is twice
+/// the area of the polygon.
+pub fn newell_normal<T:Clone + Float>(points: &[Point3<T>]) -> Vec3<T> {
+    let mut normal = Vec3::zero::<T>();
+    let n = points.len();
+    for points.iter().enumerate().advance |(i, a)| {
+        let b = &points[(i + 1) % n];
+        normal.x = normal.x + (a.y - b.y) * (a.z + b.z);
+        normal.y = normal.y + (a.z - b.z) * (a.x + b.x);
+        normal.z = normal.z + (a.x - b.x) * (a.y + b.y);
+    }
+    normal
+}
+
+/// The mean of `points`, as a position vector. At least one point must be
+/// supplied.
+fn centroid<T:Clone + Float>(points: &[Point3<T>]) -> Vec3<T> {
+    use std::num::cast;
+
+    let mut sum = Vec3::zero::<T>();
+    for points.iter().advance |p| {
+        sum.add_self_v(p.as_vec3());
+    }
+    sum.div_s(cast(points.len()))
 }
 
 impl<T> ToStr for Plane3<T> {
@@ -159,6 +525,8 @@ impl<T> ToStr for Plane3<T> {
 mod tests {
     use math::plane::*;
     use math::point::*;
+    use math::ray::*;
+    use math::vec::*;
 
     #[test]
     fn test_from_3p() {
@@ -171,17 +539,283 @@ mod tests {
                                    Point3::new(0f, 5f,  5f)), None);     // The points are parallel
     }
 
+    #[test]
+    fn test_offset() {
+        let p = Plane3::from_abcd(0.0, 0.0, 1.0, -1.0);
+        let point = Point3::new(3.0, -2.0, 5.0);
+
+        assert_eq!(p.distance(&point) - p.offset(1.5).distance(&point), 1.5);
+        assert_eq!(p.offset(-1.0).distance(&point), 5.0);
+        assert_eq!(p.with_distance(2.0), Plane3::from_abcd(0.0, 0.0, 1.0, 2.0));
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let p = Plane3::from_abcd(0.0, -2.0, 0.0, 4.0);
+
+        assert_eq!(p.normalize(), Plane3::from_abcd(0.0, -1.0, 0.0, 2.0));
+        assert_eq!(p.flip(), Plane3::from_abcd(0.0, 2.0, 0.0, -4.0));
+        assert_eq!(p.canonicalize(), Plane3::from_abcd(0.0, 1.0, 0.0, -2.0));
+        assert_eq!(p.flip().canonicalize(), p.canonicalize());
+        assert_approx_eq!(Plane3::from_abcd(3.0, 0.0, -4.0, 10.0).flip().canonicalize(),
+                          Plane3::from_abcd(0.6, 0.0, -0.8, 2.0));
+    }
+
+    #[test]
+    fn test_from_abcd_normalized() {
+        let p = Plane3::from_abcd_normalized(2.0, 0.0, 0.0, 2.0);     // x = -1
+
+        assert_eq!(p, Plane3::from_abcd(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(p.distance(&Point3::new(3.0, 5.0, -2.0)), 4.0);
+        assert_eq!(Plane3::from_abcd(2.0, 0.0, 0.0, 2.0).distance(&Point3::new(3.0, 5.0, -2.0)), 8.0);
+    }
+
+    #[test]
+    fn test_classify_sphere() {
+        let p = Plane3::from_abcd(0.0, 1.0, 0.0, -1.0);     // y = 1
+
+        assert_eq!(p.classify_sphere(&Point3::new(0.0, 3.0, 0.0), 1.0), Front);
+        assert_eq!(p.classify_sphere(&Point3::new(5.0, -2.0, 0.0), 2.5), Back);
+        assert_eq!(p.classify_sphere(&Point3::new(0.0, 1.5, 0.0), 1.0), Intersecting);
+    }
+
+    #[test]
+    fn test_reflect_point() {
+        let p = Plane3::from_abcd_normalized(1.0, 1.0, 0.0, -2.0);     // x + y = 2
+        let pos = Point3::new(0.0, 0.0, 3.0);
+        let reflected = p.reflect_point(&pos);
+
+        assert_approx_eq!(reflected, Point3::new(2.0, 2.0, 3.0));
+        assert_approx_eq!(p.reflect_point(&reflected), pos);
+
+        let v = p.reflection_matrix().mul_v(&pos.to_vec4());
+        assert_approx_eq!(v, reflected.to_vec4());
+    }
+
+    #[test]
+    fn test_fit() {
+        // points scattered about the plane `y = 2` with a little noise
+        let points = [Point3::new(0.0, 2.0,    0.0),
+                      Point3::new(1.0, 2.001,  0.0),
+                      Point3::new(0.0, 1.999,  1.0),
+                      Point3::new(1.0, 2.0,    1.0),
+                      Point3::new(0.5, 2.0005, 0.5)];
+        let p = Plane3::fit(points).unwrap();
+
+        assert!(p.normal.approx_eq_eps(&Vec3::new(0.0, 1.0, 0.0), &0.01));
+        assert!(p.distance.approx_eq_eps(&-2.0, &0.01));
+        for points.iter().advance |point| {
+            assert!(p.distance(point).abs() < 0.002);
+        }
+
+        assert_eq!(Plane3::fit([Point3::new(0.0, 0.0, 0.0),
+                                Point3::new(1.0, 0.0, 0.0)]), None);    // Too few points
+        assert_eq!(Plane3::fit([Point3::new(0.0, 0.0, 0.0),
+                                Point3::new(1.0, 1.0, 1.0),
+                                Point3::new(2.0, 2.0, 2.0)]), None);    // The points are collinear
+    }
+
+    #[test]
+    fn test_newell_normal() {
+        let square = [Point3::new(0.0, 0.0, 0.0),
+                      Point3::new(1.0, 0.0, 0.0),
+                      Point3::new(1.0, 1.0, 0.0),
+                      Point3::new(0.0, 1.0, 0.0)];
+        assert_eq!(newell_normal(square), Vec3::new(0.0, 0.0, 2.0));
+
+        // one corner is lifted slightly out of the plane
+        let quad = [Point3::new(0.0, 0.0, 0.0),
+                    Point3::new(1.0, 0.0, 0.0),
+                    Point3::new(1.0, 1.0, 0.1),
+                    Point3::new(0.0, 1.0, 0.0)];
+        let n = newell_normal(quad).normalize();
+        assert!(n.dot(&Vec3::unit_z()) > 0.99);
+    }
+
+    #[test]
+    fn test_intersection_ray_t() {
+        let p = Plane3::from_abcd(0.0, 1.0, 0.0, -2.0);     // y = 2
+
+        let ray = Ray3::new(Point3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 1.0));
+        let (t, point) = p.intersection_ray_t(&ray).unwrap();
+        assert_eq!(t, 1.0);
+        assert_eq!(point, ray.point_at(t));
+        assert_eq!(point, Point3::new(1.0, 2.0, 1.0));
+
+        // pointing away from the plane
+        assert_eq!(p.intersection_ray_t(&Ray3::new(Point3::new(0.0, 0.0, 0.0),
+                                                   Vec3::new(0.0, -1.0, 0.0))), None);
+        // parallel to the plane
+        assert_eq!(p.intersection_ray_t(&Ray3::new(Point3::new(0.0, 0.0, 0.0),
+                                                   Vec3::new(1.0, 0.0, 0.0))), None);
+    }
+
+    #[test]
+    fn test_intersection_r() {
+        let p = Plane3::from_abcd(0.0, 1.0, 0.0, 0.0);
+        let down = Ray3::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, -1.0, 0.0));
+        let up = Ray3::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 1.0, 0.0));
+
+        assert_eq!(p.intersection_r(&down), Some(Point3::new(1.0, 0.0, 3.0)));
+        assert_eq!(p.intersection_r(&up), None);
+        assert!(p.intersects(&down));
+        assert!(!p.intersects(&up));
+    }
+
+    #[test]
+    fn test_intersection_line() {
+        let p = Plane3::from_abcd(0.0, 0.0, 1.0, -1.0);     // z = 1
+
+        assert_eq!(p.intersection_line(&Point3::new(0.0, 0.0, 0.0), &Point3::new(1.0, 2.0, 2.0)),
+                   Some(Point3::new(0.5, 1.0, 1.0)));
+        // the crossing lies behind `a`
+        assert_eq!(p.intersection_line(&Point3::new(2.0, 0.0, 3.0), &Point3::new(2.0, 1.0, 5.0)),
+                   Some(Point3::new(2.0, -1.0, 1.0)));
+        // parallel to the plane
+        assert_eq!(p.intersection_line(&Point3::new(0.0, 0.0, 0.0), &Point3::new(1.0, 1.0, 0.0)), None);
+    }
+
     #[test]
     fn test_plane_intersection_3pl() {
         let p0 = Plane3::from_abcd(1.0,  0.0, 0.0, 1.0);
         let p1 = Plane3::from_abcd(0.0, -1.0, 0.0, 2.0);
         let p2 = Plane3::from_abcd(0.0,  0.0, 1.0, 1.0);
 
-        assert_eq!(p0.intersection_3pl(&p1, &p2), Some(Point3::new(1.0, -2.0, 1.0)));
+        assert_eq!(p0.intersection_3pl(&p1, &p2), Some(Point3::new(-1.0, 2.0, -1.0)));
+
+        let p = p0.intersection_3pl(&p1, &p2).unwrap();
+        assert_eq!(p0.distance(&p), 0.0);
+        assert_eq!(p1.distance(&p), 0.0);
+        assert_eq!(p2.distance(&p), 0.0);
+    }
+
+    #[test]
+    fn test_plane_intersection_2pl() {
+        let p0 = Plane3::from_abcd(1.0, 0.0, 0.0, -1.0);     // x = 1
+        let p1 = Plane3::from_abcd(0.0, 1.0, 0.0, -2.0);     // y = 2
+
+        let ray = p0.intersection_2pl(&p1).unwrap();
+        assert_eq!(ray.origin, Point3::new(1.0, 2.0, 0.0));
+        assert_eq!(ray.direction, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(p0.distance(&ray.point_at(3.0)), 0.0);
+        assert_eq!(p1.distance(&ray.point_at(3.0)), 0.0);
+
+        assert_eq!(p0.intersection_2pl(&Plane3::from_abcd(1.0, 0.0, 0.0, 4.0)), None);
+    }
+
+    #[test]
+    fn test_plane_intersection_3pl_homogeneous() {
+        let p0 = Plane3::from_abcd(1.0,  0.0, 0.0, 1.0);
+        let p1 = Plane3::from_abcd(0.0, -1.0, 0.0, 2.0);
+        let p2 = Plane3::from_abcd(0.0,  0.0, 1.0, 1.0);
+
+        let h = p0.intersection_3pl_homogeneous(&p1, &p2);
+        let p = Point3::new(h.x / h.w, h.y / h.w, h.z / h.w);
+        assert_eq!(p0.distance(&p), 0.0);
+        assert_eq!(p1.distance(&p), 0.0);
+        assert_eq!(p2.distance(&p), 0.0);
+
+        // dividing through by `w` agrees with `intersection_3pl`
+        let a = Plane3::from_abcd(1.0, 1.0, 0.0, -3.0);
+        let b = Plane3::from_abcd(0.0, 1.0, 1.0, -5.0);
+        let c = Plane3::from_abcd(1.0, 0.0, 1.0, -4.0);
+        let h = a.intersection_3pl_homogeneous(&b, &c);
+        assert_approx_eq!(Point3::new(h.x / h.w, h.y / h.w, h.z / h.w),
+                          a.intersection_3pl(&b, &c).unwrap());
+
+        // the third plane is almost parallel to the first
+        let p3 = Plane3::from_abcd(1.0, 0.0, 0.001, 3.0);
+        let h = p0.intersection_3pl_homogeneous(&p1, &p3);
+        assert!(h.w.abs() < 0.01);
+        assert!(h.z.abs() > 1.0);
     }
 
     #[test]
     fn test_to_str() {
         assert_eq!(Plane3::from_abcd(1.0, 2.0, 3.0, 4.0).to_str(), ~"1x + 2y + 3z + 4 = 0");
     }
+
+    #[test]
+    fn test_project_vector() {
+        let plane = Plane3::from_abcd(0f, 1f, 0f, -2f);
+        assert_eq!(plane.project_vector(&Vec3::new(3f, -4f, 1f)), Vec3::new(3f, 0f, 1f));
+        assert_eq!(plane.project_vector(&Vec3::new(0f, 5f, 0f)), Vec3::new(0f, 0f, 0f));
+    }
+
+    #[test]
+    fn test_intersection_segment_t() {
+        let p = Plane3::from_abcd(0.0, 0.0, 1.0, -1.0);
+        let a = Point3::new(0.0, 0.0, 0.0);
+        let b = Point3::new(2.0, 4.0, 4.0);
+
+        assert_eq!(p.intersection_segment_t(&a, &b), Some(0.25));
+        let t = p.intersection_segment_t(&a, &b).unwrap();
+        assert_eq!(a + (b - a).mul_s(t), Point3::new(0.5, 1.0, 1.0));
+        assert_eq!(Some(a + (b - a).mul_s(t)), p.intersection_line(&a, &b));
+
+        assert_eq!(p.intersection_segment_t(&a, &Point3::new(0.0, 0.0, 0.5)), None);
+        assert_eq!(p.intersection_segment_t(&a, &Point3::new(1.0, 1.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_intersects_segment() {
+        let p = Plane3::from_abcd(0.0, 0.0, 1.0, -1.0);
+
+        assert!(p.intersects_segment(&Point3::new(0.0, 0.0, 0.0), &Point3::new(1.0, 1.0, 2.0)));
+        assert!(p.intersects_segment(&Point3::new(1.0, 1.0, 2.0), &Point3::new(0.0, 0.0, 0.0)));
+        assert!(!p.intersects_segment(&Point3::new(0.0, 0.0, 0.0), &Point3::new(1.0, 1.0, 0.5)));
+        assert!(!p.intersects_segment(&Point3::new(0.0, 0.0, 2.0), &Point3::new(1.0, 1.0, 3.0)));
+        assert!(p.intersects_segment(&Point3::new(0.0, 0.0, 1.0), &Point3::new(1.0, 1.0, 3.0)));
+    }
+
+    #[test]
+    fn test_from_ray2_extruded() {
+        let ray = Ray2::new(Point2::new(1.0, 2.0), Vec2::new(1.0, 0.0));
+        let p = Plane3::from_ray2_extruded(&ray, &Vec3::new(0.0, 1.0, 0.0));
+
+        assert_eq!(p, Plane3::from_abcd(0.0, 0.0, 1.0, -2.0));
+        assert_eq!(p.distance(&Point3::new(5.0, 7.0, 2.0)), 0.0);
+        assert_eq!(p.distance(&Point3::new(5.0, 7.0, 1.0)), -1.0);
+    }
+
+    #[test]
+    fn test_basis() {
+        let planes = [Plane3::from_abcd(0.0, 1.0, 0.0, -2.0),
+                      Plane3::from_abcd(0.0, 0.0, -1.0, 1.0),
+                      Plane3::from_nd(Vec3::new(1.0, -2.0, 2.0).normalize(), 3.0)];
+
+        for planes.iter().advance |p| {
+            let (t, b) = p.basis();
+            assert_approx_eq!(t.dot(&p.normal), 0.0);
+            assert_approx_eq!(b.dot(&p.normal), 0.0);
+            assert_approx_eq!(t.dot(&b), 0.0);
+            assert_approx_eq!(t.magnitude(), 1.0);
+            assert_approx_eq!(b.magnitude(), 1.0);
+
+            let q = p.point_on_plane(1.5, -2.0);
+            assert_approx_eq!(p.distance(&q), 0.0);
+            assert_approx_eq!(q.distance(&p.point_on_plane(0.0, 0.0)), 2.5);
+        }
+    }
+
+    #[test]
+    fn test_from_coplanar_points() {
+        let a = Point3::new(1.0, 0.0, 2.0);
+        let b = Point3::new(3.0, 1.0, 0.0);
+        let c = Point3::new(0.0, 2.0, 1.0);
+        assert_approx_eq!(Plane3::from_coplanar_points([a, b, c]).unwrap(),
+                          Plane3::from_3p(a, b, c).unwrap());
+
+        // the first three vertices form a sliver, but the polygon does not
+        let a = Point3::new(0.0, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(2.0, 1.0e-8, 0.0);
+        let d = Point3::new(1.0, 2.0, 0.0);
+        assert_eq!(Plane3::from_3p(a, b, c), None);
+        assert_approx_eq!(Plane3::from_coplanar_points([a, b, c, d]).unwrap(),
+                          Plane3::from_abcd(0.0, 0.0, 1.0, 0.0));
+
+        assert_eq!(Plane3::from_coplanar_points([a, b, Point3::new(2.0, 0.0, 0.0)]), None);
+        assert_eq!(Plane3::from_coplanar_points([a, b]), None);
+    }
 }
@@ -21,9 +21,11 @@
 //! types, which allows for a clear, self-documenting API.
 
 use std::cast;
+use extra::sort;
 
-use math::{Dimensioned, SwapComponents};
+use math::{BitEq, Dimensioned, SwapComponents};
 use math::{Ray2, Ray3};
+use math::Segment2;
 use math::{Vec2, ToVec2, AsVec2};
 use math::{Vec3, ToVec3, AsVec3};
 use math::{Vec4, ToVec4};
@@ -38,9 +40,12 @@ pub trait Point<T, Vec, Ray>: Eq
     fn translate_v(&self, offset: &Vec) -> Self;
     fn scale_s(&self, factor: T) -> Self;
     fn scale_v(&self, factor: &Vec) -> Self;
+    fn div_v(&self, factor: &Vec) -> Self;
     fn displacement(&self, other: &Self) -> Vec;
     fn distance2(&self, other: &Self) -> T;
     fn distance(&self, other: &Self) -> T;
+    fn distance_manhattan(&self, other: &Self) -> T;
+    fn distance_chebyshev(&self, other: &Self) -> T;
     fn direction(&self, other: &Self) -> Vec;
     fn ray_to(&self, other: &Self) -> Ray;
 }
@@ -54,6 +59,7 @@ impl_to_vec!(Point2, 2)
 impl_as_vec!(Point2, 2)
 impl_swap_components!(Point2)
 impl_approx!(Point2 { x, y })
+impl_bit_eq!(Point2 { x, y })
 
 pub trait AsPoint2<T> {
     fn as_point2<'a>(&'a self) -> &'a Point2<T>;
@@ -92,6 +98,29 @@ impl<T:Num> Point2<T> {
     }
 }
 
+impl<T:Clone + Num> Point2<T> {
+    /// Places the point on the plane at height `z` parallel to the `xy`
+    /// plane, the inverse of `Point3::xy`.
+    #[inline]
+    pub fn to_point3(&self, z: T) -> Point3<T> {
+        Point3::new(self.x.clone(), self.y.clone(), z)
+    }
+
+    /// Places the point on the plane at height `y` parallel to the `xz`
+    /// plane, the inverse of `Point3::xz`.
+    #[inline]
+    pub fn on_xz(&self, y: T) -> Point3<T> {
+        Point3::new(self.x.clone(), y, self.y.clone())
+    }
+
+    /// Places the point on the plane at height `x` parallel to the `yz`
+    /// plane, the inverse of `Point3::yz`.
+    #[inline]
+    pub fn on_yz(&self, x: T) -> Point3<T> {
+        Point3::new(x, self.x.clone(), self.y.clone())
+    }
+}
+
 impl<T:Clone + Num> ToVec3<T> for Point2<T> {
     /// Converts the point to a three-dimensional homogeneous vector:
     /// `[x, y] -> [x, y, 1]`
@@ -126,6 +155,16 @@ impl<T:Clone + Float> Point<T, Vec2<T>, Ray2<T>> for Point2<T> {
                     self.y * factor.y)
     }
 
+    /// Divides each coordinate of the point by the corresponding component of
+    /// `factor`, undoing `scale_v`. Dividing by a zero component follows the
+    /// usual floating point rules, resulting in an infinite or `NaN`
+    /// coordinate.
+    #[inline]
+    pub fn div_v(&self, factor: &Vec2<T>) -> Point2<T> {
+        Point2::new(self.x / factor.x,
+                    self.y / factor.y)
+    }
+
     /// Calculates the displacement required to move the point to `other`.
     pub fn displacement(&self, other: &Point2<T>) -> Vec2<T> {
         Vec2::new(self.x - other.x,
@@ -147,6 +186,22 @@ impl<T:Clone + Float> Point<T, Vec2<T>, Ray2<T>> for Point2<T> {
         other.distance2(self).sqrt()
     }
 
+    /// Returns the sum of the absolute differences between the coordinates of
+    /// the point and `other`.
+    #[inline]
+    pub fn distance_manhattan(&self, other: &Point2<T>) -> T {
+        let d = self.displacement(other);
+        d.x.abs() + d.y.abs()
+    }
+
+    /// Returns the largest absolute difference between the coordinates of the
+    /// point and `other`.
+    #[inline]
+    pub fn distance_chebyshev(&self, other: &Point2<T>) -> T {
+        let d = self.displacement(other);
+        d.x.abs().max(&d.y.abs())
+    }
+
     /// Returns a normalized direction vector pointing to the other point.
     #[inline]
     pub fn direction(&self, other: &Point2<T>) -> Vec2<T> {
@@ -160,6 +215,119 @@ impl<T:Clone + Float> Point<T, Vec2<T>, Ray2<T>> for Point2<T> {
     }
 }
 
+impl<T:Clone + Float> Point2<T> {
+    /// Constructs a point from polar coordinates:
+    /// `[radius * cos(angle), radius * sin(angle)]`
+    ///
+    /// `angle` is measured from the positive `x` axis towards the positive
+    /// `y` axis.
+    #[inline]
+    pub fn from_polar(radius: T, angle: T) -> Point2<T> {
+        Point2::new(radius * angle.cos(), radius * angle.sin())
+    }
+
+    /// Converts the point to polar coordinates. See `from_polar`.
+    ///
+    /// # Return value
+    ///
+    /// A tuple containing the radius and angle, with the angle in the range
+    /// `[-pi, pi]`. The point at the origin has no defined angle, and
+    /// converts to `(0, 0)`.
+    #[inline]
+    pub fn to_polar(&self) -> (T, T) {
+        (self.as_vec2().magnitude(), self.y.atan2(&self.x))
+    }
+
+    /// Rotates the point a quarter turn counter-clockwise about the origin:
+    /// `[x, y] -> [-y, x]`
+    #[inline]
+    pub fn perp(&self) -> Point2<T> {
+        Point2::new(-self.y, self.x.clone())
+    }
+
+    /// Rotates the point a quarter turn clockwise about the origin:
+    /// `[x, y] -> [y, -x]`
+    #[inline]
+    pub fn rotate90_cw(&self) -> Point2<T> {
+        Point2::new(self.y.clone(), -self.x)
+    }
+
+    /// Rotates the point counter-clockwise about the origin by `angle` radians.
+    pub fn rotate(&self, angle: T) -> Point2<T> {
+        let c = angle.cos();
+        let s = angle.sin();
+        Point2::new(self.x * c - self.y * s,
+                    self.x * s + self.y * c)
+    }
+
+    /// Mirrors the point through `center`, equivalent to a half turn about
+    /// `center`.
+    #[inline]
+    pub fn reflect_across(&self, center: &Point2<T>) -> Point2<T> {
+        *center + (*center - *self)
+    }
+
+    /// Returns `true` if none of the coordinates are infinite or `NaN`
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    /// Returns `true` if any of the coordinates are `NaN`
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.x.is_NaN() || self.y.is_NaN()
+    }
+
+    /// Scales the point towards the origin so that it lies no further than
+    /// `max` from it. Points already within that distance are unchanged.
+    #[inline]
+    pub fn clamp_magnitude(&self, max: T) -> Point2<T> {
+        let dist = self.distance(&Point2::origin());
+        if dist > max { self.scale_s(max / dist) } else { self.clone() }
+    }
+
+    /// Returns the distance from the point to the closest point on the line
+    /// segment running from `a` to `b`. If `a` and `b` coincide, this is the
+    /// distance to `a`.
+    #[inline]
+    pub fn distance_to_segment(&self, a: &Point2<T>, b: &Point2<T>) -> T {
+        Segment2::new(a.clone(), b.clone()).closest_point(self).distance(self)
+    }
+
+    /// Computes a spatial hash of the grid cell containing the point, for
+    /// use when welding together nearby points.
+    ///
+    /// The coordinates are snapped to a grid with a spacing of `cell`, and
+    /// the integer indices of the cell are hashed. Points closer together
+    /// than `cell` usually hash equally, but two points straddling a cell
+    /// boundary will not, however close they are. Increasing `cell` welds
+    /// more distant points, and makes it more likely that separate features
+    /// collapse together.
+    ///
+    /// Points with a coordinate that is infinite, `NaN`, or too large for its
+    /// cell index to fit in an `i64`, all hash to the same value.
+    pub fn hash_quantized(&self, cell: T) -> u64 {
+        use std::hash::Hash;
+
+        match (quantize(self.x.clone(), cell.clone()), quantize(self.y.clone(), cell)) {
+            (Some(ix), Some(iy)) => (ix, iy).hash(),
+            _ => !0u64,
+        }
+    }
+}
+
+/// The index of the grid cell with a spacing of `cell` that contains `x`, or
+/// `None` if `x` is not finite or the index does not fit in an `i64`.
+fn quantize<T:Float>(x: T, cell: T) -> Option<i64> {
+    use std::num::cast;
+
+    let i = (x / cell).floor();
+    // 2^63, the first value past the end of the range of `i64`
+    let limit: T = cast(9223372036854775808.0);
+    if i.is_finite() && i >= -limit && i < limit { Some(cast(i)) } else { None }
+}
+
 impl<T:Clone + Float> Add<Vec2<T>, Point2<T>> for Point2<T> {
     /// Applies a displacement vector to the point.
     fn add(&self, offset: &Vec2<T>) -> Point2<T> {
@@ -182,6 +350,76 @@ impl<T:Clone + Float> Mul<Vec2<T>, Point2<T>> for Point2<T> {
     }
 }
 
+/// Computes the signed area of a simple polygon using the shoelace formula.
+///
+/// The area is positive if the vertices wind counter-clockwise, and negative
+/// if they wind clockwise.
+pub fn polygon_area<T:Clone + Float>(points: &[Point2<T>]) -> T {
+    let mut sum = zero!(T);
+    let n = points.len();
+    for points.iter().enumerate().advance |(i, a)| {
+        let b = &points[(i + 1) % n];
+        sum = sum + a.x * b.y - b.x * a.y;
+    }
+    sum / two!(T)
+}
+
+/// Computes twice the signed area of the triangle `abc`.
+///
+/// The result is positive if `a`, `b` and `c` wind counter-clockwise,
+/// negative if they wind clockwise, and zero if they are collinear.
+#[inline]
+pub fn orientation<T:Clone + Float>(a: &Point2<T>, b: &Point2<T>, c: &Point2<T>) -> T {
+    (*b - *a).perp_dot(&(*c - *a))
+}
+
+/// Computes the convex hull of `points` using Andrew's monotone chain
+/// algorithm.
+///
+/// The hull vertices are returned in counter-clockwise order, starting from
+/// the point with the smallest coordinates. Duplicate points and points lying
+/// on the edges of the hull are discarded, so the hull of a set of collinear
+/// points consists of its two extreme points, and fewer than three distinct
+/// points are returned as they are.
+pub fn convex_hull<T:Clone + Float>(points: &[Point2<T>]) -> ~[Point2<T>] {
+    let mut sorted = points.to_owned();
+    // order by `x`, then by `y`
+    do sort::quick_sort(sorted) |a, b| {
+        a.x < b.x || (a.x == b.x && a.y <= b.y)
+    }
+
+    let mut pts: ~[Point2<T>] = ~[];
+    for sorted.iter().advance |p| {
+        if pts.is_empty() || pts.last() != p { pts.push(p.clone()); }
+    }
+    if pts.len() < 3 { return pts; }
+
+    let mut hull: ~[Point2<T>] = ~[];
+
+    // lower hull
+    for pts.iter().advance |p| {
+        while hull.len() >= 2 &&
+              orientation(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= zero!(T) {
+            hull.pop();
+        }
+        hull.push(p.clone());
+    }
+
+    // upper hull, which must not eat into the lower hull
+    let lower_len = hull.len() + 1;
+    for pts.rev_iter().skip(1).advance |p| {
+        while hull.len() >= lower_len &&
+              orientation(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= zero!(T) {
+            hull.pop();
+        }
+        hull.push(p.clone());
+    }
+
+    // the last point is the same as the first
+    hull.pop();
+    hull
+}
+
 impl<T> ToStr for Point2<T> {
     pub fn to_str(&self) -> ~str {
         fmt!("[%?, %?]", self.x, self.y)
@@ -191,11 +429,165 @@ impl<T> ToStr for Point2<T> {
 #[cfg(test)]
 mod test_point2 {
     use math::point::*;
+    use math::vec::*;
 
     #[test]
     fn test_to_str() {
         assert_eq!(Point2::new(1, 2).to_str(), ~"[1, 2]");
     }
+
+    #[test]
+    fn test_rotate() {
+        let p = Point2::new::<float>(1.0, 0.0);
+
+        assert_eq!(p.perp(), Point2::new::<float>(0.0, 1.0));
+        assert_eq!(p.rotate90_cw(), Point2::new::<float>(0.0, -1.0));
+        assert_approx_eq!(p.rotate(Real::frac_pi_2()), Point2::new::<float>(0.0, 1.0));
+        assert_approx_eq!(p.rotate(-Real::frac_pi_2::<float>()), Point2::new::<float>(0.0, -1.0));
+        assert_approx_eq!(p.rotate(Real::pi()), Point2::new::<float>(-1.0, 0.0));
+    }
+
+    #[test]
+    fn test_distance() {
+        let a = Point2::new::<float>(1.0, 2.0);
+        let b = Point2::new::<float>(4.0, -2.0);
+
+        assert_eq!(a.distance(&b), 5.0);
+        assert_eq!(a.distance_manhattan(&b), 7.0);
+        assert_eq!(a.distance_chebyshev(&b), 4.0);
+    }
+
+    #[test]
+    fn test_reflect_across() {
+        let p = Point2::new::<float>(1.0, 2.0);
+
+        assert_eq!(p.reflect_across(&Point2::origin()), Point2::new::<float>(-1.0, -2.0));
+        assert_eq!(p.reflect_across(&Point2::new::<float>(2.0, 2.0)), Point2::new::<float>(3.0, 2.0));
+    }
+
+    #[test]
+    fn test_polygon_area() {
+        let ccw = [Point2::new::<float>(0.0, 0.0),
+                   Point2::new::<float>(1.0, 0.0),
+                   Point2::new::<float>(1.0, 1.0),
+                   Point2::new::<float>(0.0, 1.0)];
+        let cw = [Point2::new::<float>(0.0, 0.0),
+                  Point2::new::<float>(0.0, 1.0),
+                  Point2::new::<float>(1.0, 1.0),
+                  Point2::new::<float>(1.0, 0.0)];
+
+        assert_eq!(polygon_area(ccw), 1.0);
+        assert_eq!(polygon_area(cw), -1.0);
+        assert_eq!(polygon_area::<float>([]), 0.0);
+    }
+
+    #[test]
+    fn test_div_v() {
+        let p = Point2::new::<float>(4.0, -3.0);
+        let factor = Vec2::new::<float>(2.0, 0.5);
+
+        assert_eq!(p.div_v(&factor), Point2::new::<float>(2.0, -6.0));
+        assert_eq!(p.scale_v(&factor).div_v(&factor), p);
+    }
+
+    #[test]
+    fn test_hash_quantized() {
+        let a = Point2::new::<float>(1.01, 2.02);
+        let b = Point2::new::<float>(1.04, 2.09);
+        let c = Point2::new::<float>(1.11, 2.02);
+
+        assert_eq!(a.hash_quantized(0.1), b.hash_quantized(0.1));
+        assert!(a.hash_quantized(0.1) != c.hash_quantized(0.1));
+
+        // unrepresentable cells all share one bucket
+        let nan = Point2::new::<float>(Float::NaN(), 0.0);
+        let inf = Point2::new::<float>(0.0, Float::infinity());
+        let huge = Point2::new::<float>(1.0e300, 0.0);
+        assert_eq!(nan.hash_quantized(0.1), inf.hash_quantized(0.1));
+        assert_eq!(nan.hash_quantized(0.1), huge.hash_quantized(0.1));
+        assert!(nan.hash_quantized(0.1) != a.hash_quantized(0.1));
+    }
+
+    #[test]
+    fn test_distance_to_segment() {
+        let a = Point2::new::<float>(0.0, 0.0);
+        let b = Point2::new::<float>(4.0, 0.0);
+
+        assert_eq!(Point2::new::<float>(2.0, 3.0).distance_to_segment(&a, &b), 3.0);    // interior
+        assert_eq!(Point2::new::<float>(7.0, 4.0).distance_to_segment(&a, &b), 5.0);    // nearest `b`
+        assert_eq!(Point2::new::<float>(-3.0, -4.0).distance_to_segment(&a, &a), 5.0);  // degenerate
+    }
+
+    #[test]
+    fn test_clamp_magnitude() {
+        assert_eq!(Point2::new::<float>(3.0, 4.0).clamp_magnitude(10.0), Point2::new::<float>(3.0, 4.0));
+        assert_approx_eq!(Point2::new::<float>(3.0, 4.0).clamp_magnitude(2.5), Point2::new::<float>(1.5, 2.0));
+    }
+
+    #[test]
+    fn test_is_finite() {
+        let p = Point2::new::<float>(1.0, 2.0);
+        let nan = Point2::new::<float>(1.0, Float::NaN());
+        let inf = Point2::new::<float>(Float::infinity(), 2.0);
+
+        assert!(p.is_finite() && !p.is_nan());
+        assert!(!nan.is_finite() && nan.is_nan());
+        assert!(!inf.is_finite() && !inf.is_nan());
+    }
+
+    #[test]
+    fn test_to_point3() {
+        let p = Point2::new(1, 2);
+        assert_eq!(p.to_point3(5), Point3::new(1, 2, 5));
+        assert_eq!(p.on_xz(5), Point3::new(1, 5, 2));
+        assert_eq!(p.on_yz(5), Point3::new(5, 1, 2));
+        assert_eq!(p.on_xz(5).xz(), p);
+    }
+
+    #[test]
+    fn test_orientation() {
+        let a = Point2::new::<float>(0.0, 0.0);
+        let b = Point2::new::<float>(2.0, 0.0);
+        assert_eq!(orientation(&a, &b, &Point2::new(1.0, 1.0)), 2.0);
+        assert_eq!(orientation(&a, &b, &Point2::new(1.0, -1.0)), -2.0);
+        assert_eq!(orientation(&a, &b, &Point2::new(4.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let points = [Point2::new::<float>(1.0, 1.0), Point2::new::<float>(2.0, 2.0),
+                      Point2::new::<float>(0.0, 2.0), Point2::new::<float>(0.5, 1.5),
+                      Point2::new::<float>(0.0, 0.0), Point2::new::<float>(1.0, 0.0),
+                      Point2::new::<float>(2.0, 0.0), Point2::new::<float>(2.0, 2.0)];
+        assert_eq!(convex_hull(points), ~[Point2::new::<float>(0.0, 0.0), Point2::new::<float>(2.0, 0.0),
+                                          Point2::new::<float>(2.0, 2.0), Point2::new::<float>(0.0, 2.0)]);
+
+        let collinear = [Point2::new::<float>(2.0, 2.0), Point2::new::<float>(0.0, 0.0),
+                         Point2::new::<float>(3.0, 3.0), Point2::new::<float>(1.0, 1.0)];
+        assert_eq!(convex_hull(collinear), ~[Point2::new::<float>(0.0, 0.0), Point2::new::<float>(3.0, 3.0)]);
+
+        assert_eq!(convex_hull([Point2::new::<float>(1.0, 1.0), Point2::new::<float>(1.0, 1.0)]),
+                   ~[Point2::new::<float>(1.0, 1.0)]);
+        assert_eq!(convex_hull::<float>([]), ~[]);
+    }
+
+    #[test]
+    fn test_polar() {
+        let points = [Point2::new::<float>(1.0, 0.0), Point2::new::<float>(-3.0, 4.0),
+                      Point2::new::<float>(0.5, -2.0), Point2::new::<float>(-1.0, -1.0)];
+
+        for points.iter().advance |p| {
+            let (radius, angle) = p.to_polar();
+            assert_approx_eq!(Point2::from_polar(radius, angle), *p);
+        }
+
+        let (radius, angle) = Point2::new::<float>(0.0, 2.0).to_polar();
+        assert_eq!(radius, 2.0);
+        assert_approx_eq!(angle, Real::frac_pi_2());
+        assert_eq!(Point2::origin::<float>().to_polar(), (0.0, 0.0));
+        // small points keep their radius
+        assert_eq!(Point2::new::<float>(1.0e-7, 0.0).to_polar(), (1.0e-7, 0.0));
+    }
 }
 
 /// A three-dimensional coordinate vector
@@ -207,6 +599,7 @@ impl_to_vec!(Point3, 3)
 impl_as_vec!(Point3, 3)
 impl_swap_components!(Point3)
 impl_approx!(Point3 { x, y, z })
+impl_bit_eq!(Point3 { x, y, z })
 
 pub trait AsPoint3<T> {
     fn as_point3<'a>(&'a self) -> &'a Point3<T>;
@@ -245,6 +638,26 @@ impl<T:Num> Point3<T> {
     }
 }
 
+impl<T:Clone + Num> Point3<T> {
+    /// Projects the point onto the `xy` plane by dropping the `z` coordinate.
+    #[inline]
+    pub fn xy(&self) -> Point2<T> {
+        Point2::new(self.x.clone(), self.y.clone())
+    }
+
+    /// Projects the point onto the `xz` plane by dropping the `y` coordinate.
+    #[inline]
+    pub fn xz(&self) -> Point2<T> {
+        Point2::new(self.x.clone(), self.z.clone())
+    }
+
+    /// Projects the point onto the `yz` plane by dropping the `x` coordinate.
+    #[inline]
+    pub fn yz(&self) -> Point2<T> {
+        Point2::new(self.y.clone(), self.z.clone())
+    }
+}
+
 impl<T:Clone + Num> ToVec4<T> for Point3<T> {
     /// Converts the point to a four-dimensional homogeneous vector:
     /// `[x, y, z] -> [x, y, z, 1]`
@@ -283,6 +696,17 @@ impl<T:Clone + Float> Point<T, Vec3<T>, Ray3<T>> for Point3<T> {
                     self.z * factor.z)
     }
 
+    /// Divides each coordinate of the point by the corresponding component of
+    /// `factor`, undoing `scale_v`. Dividing by a zero component follows the
+    /// usual floating point rules, resulting in an infinite or `NaN`
+    /// coordinate.
+    #[inline]
+    pub fn div_v(&self, factor: &Vec3<T>) -> Point3<T> {
+        Point3::new(self.x / factor.x,
+                    self.y / factor.y,
+                    self.z / factor.z)
+    }
+
     /// Calculates the displacement required to move the point to `other`.
     pub fn displacement(&self, other: &Point3<T>) -> Vec3<T> {
         Vec3::new(self.x - other.x,
@@ -305,6 +729,22 @@ impl<T:Clone + Float> Point<T, Vec3<T>, Ray3<T>> for Point3<T> {
         other.distance2(self).sqrt()
     }
 
+    /// Returns the sum of the absolute differences between the coordinates of
+    /// the point and `other`.
+    #[inline]
+    pub fn distance_manhattan(&self, other: &Point3<T>) -> T {
+        let d = self.displacement(other);
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+
+    /// Returns the largest absolute difference between the coordinates of the
+    /// point and `other`.
+    #[inline]
+    pub fn distance_chebyshev(&self, other: &Point3<T>) -> T {
+        let d = self.displacement(other);
+        d.x.abs().max(&d.y.abs()).max(&d.z.abs())
+    }
+
     /// Returns a normalized direction vector pointing to the other point.
     #[inline]
     pub fn direction(&self, other: &Point3<T>) -> Vec3<T> {
@@ -318,6 +758,251 @@ impl<T:Clone + Float> Point<T, Vec3<T>, Ray3<T>> for Point3<T> {
     }
 }
 
+impl<T:Clone + Float> Point3<T> {
+    /// Computes the weighted sum of `points`, scaling each point by the
+    /// weight at the same index in `weights`.
+    ///
+    /// When the weights sum to `1` this is an affine combination, such as a
+    /// barycentric interpolation or a centroid, and the result is independent
+    /// of the choice of origin. Otherwise it is a linear combination of the
+    /// position vectors of the points, and the result depends on where the
+    /// origin lies.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `weights` and `points` are of different lengths.
+    pub fn barycentric_combine(weights: &[T], points: &[Point3<T>]) -> Point3<T> {
+        assert!(weights.len() == points.len(),
+                "the number of weights must match the number of points");
+        let mut sum = Vec3::zero::<T>();
+        for weights.iter().zip(points.iter()).advance |(w, p)| {
+            sum.add_self_v(&p.as_vec3().mul_s(w.clone()));
+        }
+        Point3::from_vec3(sum)
+    }
+
+    /// Evaluates the uniform Catmull-Rom spline segment that passes through
+    /// `p1` at `t == 0` and `p2` at `t == 1`. The neighbouring control points
+    /// `p0` and `p3` determine the tangents at either end, so consecutive
+    /// segments of a path join smoothly.
+    pub fn catmull_rom(p0: &Point3<T>, p1: &Point3<T>, p2: &Point3<T>, p3: &Point3<T>,
+                       t: T) -> Point3<T> {
+        let _2: T = two!(T);
+        let _3 = _2 + one!(T);
+        let _4 = _2 * _2;
+        let _5 = _4 + one!(T);
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        let weights = [(-t3 + _2 * t2 - t) / _2,
+                       (_3 * t3 - _5 * t2 + _2) / _2,
+                       (-_3 * t3 + _4 * t2 + t) / _2,
+                       (t3 - t2) / _2];
+        Point3::barycentric_combine(weights, [p0.clone(), p1.clone(), p2.clone(), p3.clone()])
+    }
+
+    /// Evaluates the quadratic Bézier curve from `p0` to `p2`, with the
+    /// control point `p1`, using De Casteljau's algorithm.
+    pub fn bezier2(p0: &Point3<T>, p1: &Point3<T>, p2: &Point3<T>, t: T) -> Point3<T> {
+        let a = lerp_point3(p0, p1, t.clone());
+        let b = lerp_point3(p1, p2, t.clone());
+        lerp_point3(&a, &b, t)
+    }
+
+    /// Evaluates the cubic Bézier curve from `p0` to `p3`, with the control
+    /// handles `p1` and `p2`, using De Casteljau's algorithm.
+    pub fn bezier3(p0: &Point3<T>, p1: &Point3<T>, p2: &Point3<T>, p3: &Point3<T>,
+                   t: T) -> Point3<T> {
+        let a = lerp_point3(p0, p1, t.clone());
+        let b = lerp_point3(p1, p2, t.clone());
+        let c = lerp_point3(p2, p3, t.clone());
+        Point3::bezier2(&a, &b, &c, t)
+    }
+
+    /// Evaluates the cubic Hermite curve that starts at `p0` with the tangent
+    /// `m0` when `t == 0`, and ends at `p1` with the tangent `m1` when
+    /// `t == 1`.
+    pub fn hermite(p0: &Point3<T>, m0: &Vec3<T>, p1: &Point3<T>, m1: &Vec3<T>,
+                   t: T) -> Point3<T> {
+        let _2: T = two!(T);
+        let _3 = _2 + one!(T);
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        let h00 = _2 * t3 - _3 * t2 + one!(T);
+        let h10 = t3 - _2 * t2 + t;
+        let h01 = _3 * t2 - _2 * t3;
+        let h11 = t3 - t2;
+
+        Point3::from_vec3(p0.as_vec3().mul_s(h00)
+                                      .add_v(&m0.mul_s(h10))
+                                      .add_v(&p1.as_vec3().mul_s(h01))
+                                      .add_v(&m1.mul_s(h11)))
+    }
+
+    /// Constructs a point from spherical coordinates, using `y` as the up
+    /// axis.
+    ///
+    /// # Arguments
+    ///
+    /// - `radius`: the distance from the origin
+    /// - `theta`: the inclination, measured from the positive `y` axis
+    /// - `phi`: the azimuth, measured from the positive `x` axis towards the
+    ///          positive `z` axis
+    pub fn from_spherical(radius: T, theta: T, phi: T) -> Point3<T> {
+        let (sin_theta, cos_theta) = (theta.sin(), theta.cos());
+        Point3::new(radius * sin_theta * phi.cos(),
+                    radius * cos_theta,
+                    radius * sin_theta * phi.sin())
+    }
+
+    /// Converts the point to spherical coordinates. See `from_spherical`.
+    ///
+    /// # Return value
+    ///
+    /// A tuple containing the radius, inclination and azimuth. The point at
+    /// the origin has no defined angles, and converts to `(0, 0, 0)`.
+    pub fn to_spherical(&self) -> (T, T, T) {
+        let radius = self.as_vec3().magnitude();
+        let theta = if radius == zero!(T) {
+            zero!(T)
+        } else {
+            (self.y / radius).clamp(&-one!(T), &one!(T)).acos()
+        };
+        let phi = self.z.atan2(&self.x);
+        (radius, theta, phi)
+    }
+
+    /// Constructs a point from cylindrical coordinates:
+    /// `[r * cos(theta), height, r * sin(theta)]`
+    ///
+    /// The axis of the cylinder is the `y` axis, so `height` is measured
+    /// upwards along `y`, and `theta` is measured in the `xz` plane from the
+    /// positive `x` axis towards the positive `z` axis.
+    pub fn from_cylindrical(radius: T, theta: T, height: T) -> Point3<T> {
+        Point3::new(radius * theta.cos(), height, radius * theta.sin())
+    }
+
+    /// Converts the point to cylindrical coordinates. See `from_cylindrical`.
+    ///
+    /// # Return value
+    ///
+    /// A tuple containing the radius, angle and height. Points on the `y`
+    /// axis have no defined angle, and are given an angle of `0`.
+    pub fn to_cylindrical(&self) -> (T, T, T) {
+        let radius = (self.x * self.x + self.z * self.z).sqrt();
+        (radius, self.z.atan2(&self.x), self.y.clone())
+    }
+
+    /// Returns the coordinate along the axis with the index `axis`, where `0`,
+    /// `1` and `2` correspond to the `x`, `y` and `z` axes.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `axis` is greater than `2`.
+    #[inline]
+    pub fn axis(&self, axis: uint) -> T {
+        match axis {
+            0 => self.x.clone(),
+            1 => self.y.clone(),
+            2 => self.z.clone(),
+            _ => fail!("invalid axis index: %u", axis),
+        }
+    }
+
+    /// Remaps the axes of the point, reading the new `x`, `y` and `z`
+    /// coordinates from the axes with the indices `x_from`, `y_from` and
+    /// `z_from`. This is useful for converting between coordinate
+    /// conventions, in combination with `negate_axis`.
+    ///
+    /// # Failure
+    ///
+    /// Fails if any of the indices are greater than `2`.
+    #[inline]
+    pub fn permute(&self, x_from: uint, y_from: uint, z_from: uint) -> Point3<T> {
+        Point3::new(self.axis(x_from), self.axis(y_from), self.axis(z_from))
+    }
+
+    /// Negates the coordinate along the axis with the index `axis`.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `axis` is greater than `2`.
+    #[inline]
+    pub fn negate_axis(&self, axis: uint) -> Point3<T> {
+        let mut p = self.clone();
+        match axis {
+            0 => p.x = -p.x,
+            1 => p.y = -p.y,
+            2 => p.z = -p.z,
+            _ => fail!("invalid axis index: %u", axis),
+        }
+        p
+    }
+
+    /// Mirrors the point through `center`, equivalent to a half turn about
+    /// `center`.
+    #[inline]
+    pub fn reflect_across(&self, center: &Point3<T>) -> Point3<T> {
+        *center + (*center - *self)
+    }
+
+    /// Returns `true` if none of the coordinates are infinite or `NaN`
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Returns `true` if any of the coordinates are `NaN`
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.x.is_NaN() || self.y.is_NaN() || self.z.is_NaN()
+    }
+
+    /// Scales the point towards the origin so that it lies no further than
+    /// `max` from it. Points already within that distance are unchanged.
+    #[inline]
+    pub fn clamp_magnitude(&self, max: T) -> Point3<T> {
+        let dist = self.distance(&Point3::origin());
+        if dist > max { self.scale_s(max / dist) } else { self.clone() }
+    }
+
+    /// Linearly interpolates towards `other` by `amount`, but moves no further
+    /// than `max_step`. Useful for smoothly following a target that may jump
+    /// a long way in a single update.
+    pub fn lerp_capped(&self, other: &Point3<T>, amount: T, max_step: T) -> Point3<T> {
+        let step = (*other - *self).mul_s(amount);
+        if step.magnitude() > max_step {
+            *self + step.normalize_to(max_step)
+        } else {
+            *self + step
+        }
+    }
+
+    /// Computes a spatial hash of the grid cell containing the point, for
+    /// use when welding together nearby points.
+    ///
+    /// The coordinates are snapped to a grid with a spacing of `cell`, and
+    /// the integer indices of the cell are hashed. Points closer together
+    /// than `cell` usually hash equally, but two points straddling a cell
+    /// boundary will not, however close they are. Increasing `cell` welds
+    /// more distant points, and makes it more likely that separate features
+    /// collapse together.
+    ///
+    /// Points with a coordinate that is infinite, `NaN`, or too large for its
+    /// cell index to fit in an `i64`, all hash to the same value.
+    pub fn hash_quantized(&self, cell: T) -> u64 {
+        use std::hash::Hash;
+
+        match (quantize(self.x.clone(), cell.clone()),
+               quantize(self.y.clone(), cell.clone()),
+               quantize(self.z.clone(), cell)) {
+            (Some(ix), Some(iy), Some(iz)) => (ix, iy, iz).hash(),
+            _ => !0u64,
+        }
+    }
+}
+
 impl<T:Clone + Float> Add<Vec3<T>, Point3<T>> for Point3<T> {
     /// Applies a displacement vector to the point
     fn add(&self, offset: &Vec3<T>) -> Point3<T> {
@@ -340,6 +1025,38 @@ impl<T:Clone + Float> Mul<Vec3<T>, Point3<T>> for Point3<T> {
     }
 }
 
+/// Linearly interpolates between `a` and `b`, returning them exactly when
+/// `t` is `0` or `1`
+fn lerp_point3<T:Clone + Float>(a: &Point3<T>, b: &Point3<T>, t: T) -> Point3<T> {
+    Point3::from_vec3(a.as_vec3().mul_s(one!(T) - t).add_v(&b.as_vec3().mul_s(t)))
+}
+
+/// Determines on which side of the plane through `a`, `b` and `c` the point
+/// `d` lies, as the determinant of the vectors from `a` to the other points.
+///
+/// The result is positive when `d` lies on the side of the triangle `abc`
+/// from which its vertices appear counter-clockwise, negative when it lies on
+/// the other side, and zero when the four points are coplanar. Its magnitude
+/// is six times the volume of the tetrahedron `abcd`.
+#[inline]
+pub fn orient3d<T:Clone + Float>(a: &Point3<T>, b: &Point3<T>,
+                                 c: &Point3<T>, d: &Point3<T>) -> T {
+    (*b - *a).dot(&(*c - *a).cross(&(*d - *a)))
+}
+
+/// Computes the signed volume of the tetrahedron formed from the points `a`,
+/// `b`, `c` and `d`.
+///
+/// The volume is positive when `d` lies on the side of the triangle `abc`
+/// from which its vertices appear counter-clockwise, and negative otherwise.
+pub fn tetrahedron_volume<T:Clone + Float>(a: &Point3<T>, b: &Point3<T>,
+                                           c: &Point3<T>, d: &Point3<T>) -> T {
+    use std::num::cast;
+
+    let six: T = cast(6);
+    orient3d(a, b, c, d) / six
+}
+
 impl<T> ToStr for Point3<T> {
     pub fn to_str(&self) -> ~str {
         fmt!("[%?, %?, %?]", self.x, self.y, self.z)
@@ -348,10 +1065,270 @@ impl<T> ToStr for Point3<T> {
 
 #[cfg(test)]
 mod test_point3 {
+    use math::{ApproxEqRel, BitEq};
     use math::point::*;
+    use math::vec::*;
 
     #[test]
     fn test_to_str() {
         assert_eq!(Point3::new(1, 2, 3).to_str(), ~"[1, 2, 3]");
     }
+
+    #[test]
+    fn test_distance() {
+        let a = Point3::new::<float>(1.0, 2.0, 3.0);
+        let b = Point3::new::<float>(4.0, -2.0, 8.0);
+
+        assert_eq!(a.distance2(&b), 50.0);
+        assert_eq!(a.distance_manhattan(&b), 12.0);
+        assert_eq!(a.distance_chebyshev(&b), 5.0);
+    }
+
+    #[test]
+    fn test_reflect_across() {
+        let p = Point3::new::<float>(1.0, 1.0, 1.0);
+
+        assert_eq!(p.reflect_across(&Point3::origin()), Point3::new::<float>(-1.0, -1.0, -1.0));
+        assert_eq!(p.reflect_across(&Point3::new::<float>(1.0, 2.0, 0.0)), Point3::new::<float>(1.0, 3.0, -1.0));
+    }
+
+    #[test]
+    fn test_spherical() {
+        let points = [Point3::new::<float>(1.0, 2.0, 3.0),
+                      Point3::new::<float>(-4.0, 0.5, 1.0),
+                      Point3::new::<float>(0.0, -1.0, -2.0)];
+        for points.iter().advance |p| {
+            let (r, theta, phi) = p.to_spherical();
+            assert_approx_eq!(Point3::from_spherical(r, theta, phi), *p);
+        }
+
+        assert_approx_eq!(Point3::from_spherical::<float>(2.0, Real::frac_pi_2(), 0.0),
+                          Point3::new::<float>(2.0, 0.0, 0.0));
+        assert_eq!(Point3::origin::<float>().to_spherical(), (0.0, 0.0, 0.0));
+        // small points keep their radius
+        assert_eq!(Point3::new::<float>(0.0, 1.0e-7, 0.0).to_spherical(), (1.0e-7, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_cylindrical() {
+        let points = [Point3::new::<float>(1.0, 2.0, 3.0),
+                      Point3::new::<float>(-4.0, 0.5, -1.0),
+                      Point3::new::<float>(0.0, -3.0, 2.0),
+                      Point3::new::<float>(-0.5, 0.0, 0.0)];
+        for points.iter().advance |p| {
+            let (r, theta, h) = p.to_cylindrical();
+            assert_approx_eq!(Point3::from_cylindrical(r, theta, h), *p);
+        }
+
+        assert_approx_eq!(Point3::from_cylindrical::<float>(2.0, Real::frac_pi_2(), 5.0),
+                          Point3::new::<float>(0.0, 5.0, 2.0));
+        assert_eq!(Point3::new::<float>(3.0, 4.0, 0.0).to_cylindrical(), (3.0, 0.0, 4.0));
+        // on the axis
+        assert_eq!(Point3::new::<float>(0.0, 7.0, 0.0).to_cylindrical(), (0.0, 0.0, 7.0));
+        // close to the axis
+        assert_eq!(Point3::new::<float>(1.0e-7, 3.0, 0.0).to_cylindrical(), (1.0e-7, 0.0, 3.0));
+    }
+
+    #[test]
+    fn test_bit_eq() {
+        let pos = Point3::new::<float>(0.0, 1.0, 2.0);
+        let neg = Point3::new::<float>(-0.0, 1.0, 2.0);
+        assert_eq!(pos, neg);
+        assert!(!pos.bit_eq(&neg));
+        assert!(pos.bit_eq(&pos));
+
+        let nan = Point3::new::<float>(Float::NaN(), 1.0, 2.0);
+        assert!(nan != nan);
+        assert!(nan.bit_eq(&nan));
+    }
+
+    #[test]
+    fn test_tetrahedron_volume() {
+        let a = Point3::new::<float>(0.0, 0.0, 0.0);
+        let b = Point3::new::<float>(1.0, 0.0, 0.0);
+        let c = Point3::new::<float>(0.0, 1.0, 0.0);
+        let d = Point3::new::<float>(0.0, 0.0, 1.0);
+
+        assert_approx_eq!(tetrahedron_volume(&a, &b, &c, &d), 1.0 / 6.0);
+        assert_approx_eq!(tetrahedron_volume(&a, &c, &b, &d), -1.0 / 6.0);
+        assert_eq!(tetrahedron_volume(&a, &b, &c, &Point3::new(0.5, 0.5, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_orient3d() {
+        let a = Point3::new::<float>(1.0, 0.0, 2.0);
+        let b = Point3::new::<float>(3.0, 0.0, 2.0);
+        let c = Point3::new::<float>(1.0, 2.0, 2.0);
+
+        assert_eq!(orient3d(&a, &b, &c, &Point3::new(5.0, -3.0, 3.0)), 4.0);
+        assert_eq!(orient3d(&a, &b, &c, &Point3::new(5.0, -3.0, 1.0)), -4.0);
+        assert_eq!(orient3d(&a, &b, &c, &Point3::new(5.0, -3.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn test_div_v() {
+        let p = Point3::new::<float>(4.0, -3.0, 1.0);
+        let factor = Vec3::new::<float>(2.0, 0.5, 4.0);
+
+        assert_eq!(p.div_v(&factor), Point3::new::<float>(2.0, -6.0, 0.25));
+        assert_eq!(p.scale_v(&factor).div_v(&factor), p);
+    }
+
+    #[test]
+    fn test_hash_quantized() {
+        let a = Point3::new::<float>(1.01, 2.02, -3.03);
+        let b = Point3::new::<float>(1.04, 2.09, -3.01);
+        let c = Point3::new::<float>(1.01, 2.02, -2.99);
+
+        assert_eq!(a.hash_quantized(0.1), b.hash_quantized(0.1));
+        assert!(a.hash_quantized(0.1) != c.hash_quantized(0.1));
+
+        // unrepresentable cells all share one bucket
+        let nan = Point3::new::<float>(Float::NaN(), 0.0, 0.0);
+        let inf = Point3::new::<float>(0.0, 0.0, Float::infinity());
+        let huge = Point3::new::<float>(0.0, 1.0e300, 0.0);
+        assert_eq!(nan.hash_quantized(0.1), inf.hash_quantized(0.1));
+        assert_eq!(nan.hash_quantized(0.1), huge.hash_quantized(0.1));
+        assert!(nan.hash_quantized(0.1) != a.hash_quantized(0.1));
+    }
+
+    #[test]
+    fn test_axis() {
+        let p = Point3::new::<float>(1.0, 2.0, 3.0);
+
+        assert_eq!(p.axis(0), 1.0);
+        assert_eq!(p.axis(1), 2.0);
+        assert_eq!(p.axis(2), 3.0);
+    }
+
+    #[test]
+    #[should_fail]
+    fn test_axis_out_of_range() {
+        Point3::new::<float>(1.0, 2.0, 3.0).axis(3);
+    }
+
+    #[test]
+    fn test_clamp_magnitude() {
+        assert_eq!(Point3::new::<float>(1.0, 2.0, 2.0).clamp_magnitude(3.0), Point3::new::<float>(1.0, 2.0, 2.0));
+        assert_approx_eq!(Point3::new::<float>(1.0, 2.0, 2.0).clamp_magnitude(1.5), Point3::new::<float>(0.5, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_is_finite() {
+        let p = Point3::new::<float>(1.0, 2.0, 3.0);
+        let nan = Point3::new::<float>(1.0, 2.0, Float::NaN());
+        let inf = Point3::new::<float>(1.0, Float::neg_infinity(), 3.0);
+
+        assert!(p.is_finite() && !p.is_nan());
+        assert!(!nan.is_finite() && nan.is_nan());
+        assert!(!inf.is_finite() && !inf.is_nan());
+    }
+
+    #[test]
+    fn test_permute() {
+        let p = Point3::new::<float>(1.0, 2.0, 3.0);
+
+        assert_eq!(p.permute(2, 0, 1), Point3::new::<float>(3.0, 1.0, 2.0));
+        assert_eq!(p.negate_axis(1), Point3::new::<float>(1.0, -2.0, 3.0));
+        // from z-up to y-up
+        assert_eq!(p.permute(0, 2, 1).negate_axis(2), Point3::new::<float>(1.0, 3.0, -2.0));
+    }
+
+    #[test]
+    fn test_barycentric_combine() {
+        let a = Point3::new::<float>(0.0, 0.0, 0.0);
+        let b = Point3::new::<float>(4.0, 0.0, 2.0);
+        let c = Point3::new::<float>(0.0, 8.0, -2.0);
+
+        // a point on the edge `bc`
+        let bc = Point3::from_vec3(b.as_vec3().lerp(c.as_vec3(), 0.25));
+        assert_eq!(Point3::barycentric_combine([0.0, 0.75, 0.25], [a, b, c]), bc);
+
+        // the centroid
+        let third = 1.0 / 3.0;
+        assert_approx_eq!(Point3::barycentric_combine([third, third, third], [a, b, c]),
+                          Point3::new::<float>(4.0 / 3.0, 8.0 / 3.0, 0.0));
+    }
+
+    #[test]
+    fn test_approx_eq_rel() {
+        let a = Point3::new::<float>(1.0e6, -2.0e6, 3.0e6);
+        let b = Point3::new::<float>(1.0e6 + 0.5, -2.0e6 - 0.5, 3.0e6 + 0.5);
+
+        assert!(!a.approx_eq(&b));
+        assert!(a.approx_eq_rel(&b, &1.0e-6));
+        assert!(!a.approx_eq_rel(&b, &1.0e-8));
+        assert!(Point3::origin::<float>().approx_eq_rel(&Point3::origin(), &1.0e-6));
+
+        // components near zero are measured against the scale of the point
+        let c = Point3::new::<float>(1.0e6, 0.0, 0.0);
+        let d = Point3::new::<float>(1.0e6, 1.0e-12, 0.0);
+        assert!(c.approx_eq_rel(&d, &1.0e-6));
+    }
+
+    #[test]
+    fn test_drop_axis() {
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p.xy(), Point2::new(1, 2));
+        assert_eq!(p.xz(), Point2::new(1, 3));
+        assert_eq!(p.yz(), Point2::new(2, 3));
+    }
+
+    #[test]
+    fn test_catmull_rom() {
+        let p0 = Point3::new::<float>(-1.0, 0.0, 0.0);
+        let p1 = Point3::new::<float>(0.0, 0.0, 0.0);
+        let p2 = Point3::new::<float>(1.0, 0.0, 0.0);
+        let p3 = Point3::new::<float>(2.0, 3.0, 0.0);
+
+        assert_eq!(Point3::catmull_rom(&p0, &p1, &p2, &p3, 0.0), p1);
+        assert_eq!(Point3::catmull_rom(&p0, &p1, &p2, &p3, 1.0), p2);
+
+        // pulled away from the straight line between `p1` and `p2` by `p3`
+        let mid = Point3::catmull_rom(&p0, &p1, &p2, &p3, 0.5);
+        assert_approx_eq!(mid, Point3::new::<float>(0.5, -0.1875, 0.0));
+    }
+
+    #[test]
+    fn test_bezier() {
+        let p0 = Point3::new::<float>(0.0, 0.0, 0.0);
+        let p1 = Point3::new::<float>(0.0, 1.0, 0.0);
+        let p2 = Point3::new::<float>(1.0, 1.0, 0.0);
+        let p3 = Point3::new::<float>(1.0, 0.0, 0.0);
+
+        assert_eq!(Point3::bezier3(&p0, &p1, &p2, &p3, 0.0), p0);
+        assert_eq!(Point3::bezier3(&p0, &p1, &p2, &p3, 1.0), p3);
+        assert_eq!(Point3::bezier3(&p0, &p1, &p2, &p3, 0.5), Point3::new::<float>(0.5, 0.75, 0.0));
+
+        let q1 = Point3::new::<float>(1.0, 2.0, 0.0);
+        assert_eq!(Point3::bezier2(&p0, &q1, &p3, 0.0), p0);
+        assert_eq!(Point3::bezier2(&p0, &q1, &p3, 1.0), p3);
+        assert_eq!(Point3::bezier2(&p0, &q1, &p3, 0.5), Point3::new::<float>(0.75, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_lerp_capped() {
+        let a = Point3::new::<float>(0.0, 0.0, 0.0);
+        let b = Point3::new::<float>(0.0, 8.0, 0.0);
+
+        assert_eq!(a.lerp_capped(&b, 0.25, 4.0), Point3::new::<float>(0.0, 2.0, 0.0));
+        assert_approx_eq!(a.lerp_capped(&b, 0.75, 4.0), Point3::new::<float>(0.0, 4.0, 0.0));
+        assert_eq!(b.lerp_capped(&a, 1.0, 1.0), Point3::new::<float>(0.0, 7.0, 0.0));
+    }
+
+    #[test]
+    fn test_hermite() {
+        let p0 = Point3::new::<float>(1.0, 0.0, -1.0);
+        let p1 = Point3::new::<float>(4.0, 2.0, 0.0);
+        let m0 = Vec3::new::<float>(0.0, 5.0, 1.0);
+        let m1 = Vec3::new::<float>(-2.0, 0.0, 3.0);
+
+        assert_eq!(Point3::hermite(&p0, &m0, &p1, &m1, 0.0), p0);
+        assert_eq!(Point3::hermite(&p0, &m0, &p1, &m1, 1.0), p1);
+
+        // the curve leaves `p0` along `m0`
+        let h = 1.0e-5;
+        let d = Point3::hermite(&p0, &m0, &p1, &m1, h) - p0;
+        assert!(d.div_s(h).approx_eq_eps(&m0, &1.0e-3));
+    }
 }
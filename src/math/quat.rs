@@ -15,9 +15,13 @@
 
 //! Quaternion type
 
-use math::{Dimensioned, SwapComponents};
+use std::cmp::{Ordering, Less, Equal, Greater};
+
+use math::{BitEq, Dimensioned, SwapComponents};
 use math::{Mat3, ToMat3};
+use math::Mat4;
 use math::Vec3;
+use math::{Vec4, ToVec4};
 
 // GLSL-style type aliases
 
@@ -36,7 +40,8 @@ pub struct Quat<T> { s: T, v: Vec3<T> }
 
 impl_dimensioned!(Quat, T, 4)
 impl_swap_components!(Quat)
-impl_approx!(Quat { s, v })
+impl_approx!(Quat { s, v } eps = 1.0e-5)
+impl_bit_eq!(Quat { s, v })
 
 pub trait ToQuat<T> {
     fn to_quat(&self) -> Quat<T>;
@@ -67,6 +72,31 @@ impl<T> Quat<T> {
     pub fn from_sv(s: T, v: Vec3<T>) -> Quat<T> {
         Quat { s: s, v: v }
     }
+
+    /// Construct the quaternion from a four-dimensional vector, taking the
+    /// components in the order `[x, y, z, w]`, where `w` is the scalar
+    /// component. This is the inverse of `to_vec4`.
+    #[inline]
+    pub fn from_vec4(vec: Vec4<T>) -> Quat<T> {
+        let Vec4 { x, y, z, w } = vec;
+        Quat::new(w, x, y, z)
+    }
+}
+
+impl<T:Clone> Quat<T> {
+    /// The components of the quaternion in the order `[x, y, z, w]`, where
+    /// `w` is the scalar component, as expected by most graphics APIs.
+    #[inline]
+    pub fn to_array_xyzw(&self) -> [T, ..4] {
+        [self.v.x.clone(), self.v.y.clone(), self.v.z.clone(), self.s.clone()]
+    }
+
+    /// The components of the quaternion in the order `[w, x, y, z]`, where
+    /// `w` is the scalar component. This is the order taken by `Quat::new`.
+    #[inline]
+    pub fn to_array_wxyz(&self) -> [T, ..4] {
+        [self.s.clone(), self.v.x.clone(), self.v.y.clone(), self.v.z.clone()]
+    }
 }
 
 impl<T:Clone + Float> Quat<T> {
@@ -75,11 +105,121 @@ impl<T:Clone + Float> Quat<T> {
         Mat3::look_at(dir, up).to_quat()
     }
 
+    /// Constructs a left-handed look-at rotation. See `Mat3::look_at_lh`.
+    #[inline]
+    pub fn look_at_lh(dir: &Vec3<T>, up: &Vec3<T>) -> Quat<T> {
+        Mat3::look_at_lh(dir, up).to_quat()
+    }
+
+    /// Constructs a right-handed look-at rotation. See `Mat3::look_at_rh`.
+    #[inline]
+    pub fn look_at_rh(dir: &Vec3<T>, up: &Vec3<T>) -> Quat<T> {
+        Mat3::look_at_rh(dir, up).to_quat()
+    }
+
     #[inline]
     pub fn from_axes(x: Vec3<T>, y: Vec3<T>, z: Vec3<T>) -> Quat<T> {
         Mat3::from_axes(x, y, z).to_quat()
     }
 
+    /// Constructs the rotation that maps the `x`, `y` and `z` axes onto the
+    /// columns `m.x`, `m.y` and `m.z` of the matrix respectively. The rows of
+    /// the matrix are not used as the axes; passing the transpose yields the
+    /// inverse rotation.
+    ///
+    /// # Failure
+    ///
+    /// When built with `--cfg debug`, fails if the columns are not an
+    /// orthonormal, right-handed basis to within the `approx_epsilon` of
+    /// `Quat`. Otherwise the basis is not checked.
+    pub fn from_basis_columns(m: &Mat3<T>) -> Quat<T> {
+        check_basis_columns(m);
+        m.to_quat()
+    }
+
+    /// Constructs a rotation of `radians` about the unit length `axis`
+    #[inline]
+    pub fn from_angle_axis(radians: T, axis: &Vec3<T>) -> Quat<T> {
+        let half = radians / two!(T);
+        Quat::from_sv(half.cos(), axis.mul_s(half.sin()))
+    }
+
+    /// Constructs a rotation of `degrees` about the unit length `axis`. This
+    /// is the same as `from_angle_axis`, but with the angle in degrees.
+    #[inline]
+    pub fn from_angle_axis_degrees(degrees: T, axis: &Vec3<T>) -> Quat<T> {
+        Quat::from_angle_axis(degrees.to_radians(), axis)
+    }
+
+    /// Constructs a rotation of `angle` radians about the `x` axis
+    #[inline]
+    pub fn rotation_x(angle: T) -> Quat<T> {
+        let half = angle / two!(T);
+        Quat::new(half.cos(), half.sin(), zero!(T), zero!(T))
+    }
+
+    /// Constructs a rotation of `angle` radians about the `y` axis
+    #[inline]
+    pub fn rotation_y(angle: T) -> Quat<T> {
+        let half = angle / two!(T);
+        Quat::new(half.cos(), zero!(T), half.sin(), zero!(T))
+    }
+
+    /// Constructs a rotation of `angle` radians about the `z` axis
+    #[inline]
+    pub fn rotation_z(angle: T) -> Quat<T> {
+        let half = angle / two!(T);
+        Quat::new(half.cos(), zero!(T), zero!(T), half.sin())
+    }
+
+    /// Constructs a camera rotation from a yaw about the world `y` axis,
+    /// followed by a pitch about the camera's local `x` axis. The rotation
+    /// never introduces any roll.
+    ///
+    /// The camera is assumed to look down the negative `z` axis, so a positive
+    /// yaw turns it to the left and a positive pitch tilts it upwards. `pitch`
+    /// is clamped to stay just short of `pi/2` in either direction, which
+    /// prevents the camera from flipping over the poles and keeps the forward
+    /// direction from becoming parallel to the world `y` axis.
+    pub fn from_yaw_pitch(yaw: T, pitch: T) -> Quat<T> {
+        use std::num::cast;
+        let limit = Real::frac_pi_2::<T>() - cast(1.0e-4);
+        let half_yaw = yaw / two!(T);
+        let half_pitch = pitch.clamp(&-limit, &limit) / two!(T);
+
+        let q_yaw = Quat::new(half_yaw.cos(), zero!(T), half_yaw.sin(), zero!(T));
+        let q_pitch = Quat::new(half_pitch.cos(), half_pitch.sin(), zero!(T), zero!(T));
+        q_yaw.mul_q(&q_pitch)
+    }
+
+    /// Extracts the rotation from the upper-left 3 x 3 block of a
+    /// transformation matrix, ignoring the translation.
+    ///
+    /// The matrix is assumed to contain no shear or scale. Non-uniform scale
+    /// will corrupt the result, so the basis axes should be normalized
+    /// beforehand if it might be present.
+    pub fn from_mat4(m: &Mat4<T>) -> Quat<T> {
+        Mat3::new(m.x.x.clone(), m.x.y.clone(), m.x.z.clone(),
+                  m.y.x.clone(), m.y.y.clone(), m.y.z.clone(),
+                  m.z.x.clone(), m.z.y.clone(), m.z.z.clone()).to_quat()
+    }
+
+    /// Decomposes a matrix containing a rotation and a scale along each of
+    /// its axes, such as those exported from modelling tools.
+    ///
+    /// # Return value
+    ///
+    /// A tuple containing the rotation, and the scale that was removed from
+    /// each column of the matrix before converting it. The matrix is assumed
+    /// to contain no shear.
+    pub fn from_mat3_unscaled(m: &Mat3<T>) -> (Quat<T>, Vec3<T>) {
+        let scale = Vec3::new(m.x.magnitude(), m.y.magnitude(), m.z.magnitude());
+        let rot = Mat3::from_axes(m.x.div_s(scale.x.clone()),
+                                  m.y.div_s(scale.y.clone()),
+                                  m.z.div_s(scale.z.clone()));
+        (rot.to_quat(), scale)
+    }
+
     /// The multiplicative identity, ie: `q = 1 + 0i + 0j + 0i`
     #[inline]
     pub fn identity() -> Quat<T> {
@@ -111,6 +251,25 @@ impl<T:Clone + Float> Quat<T> {
         self.v.cross(&tmp).mul_s(two!(T)).add_v(vec)
     }
 
+    /// Rotates each vector in `vecs` in place, sharing the work that depends
+    /// only on the quaternion between all of the vectors. The result is the
+    /// same as calling `mul_v` on each vector.
+    pub fn rotate_all_mut(&self, vecs: &mut [Vec3<T>]) {
+        let v2 = self.v.mul_s(two!(T));
+        for vecs.mut_iter().advance |vec| {
+            let tmp = self.v.cross(vec).add_v(&vec.mul_s(self.s.clone()));
+            *vec = v2.cross(&tmp).add_v(vec);
+        }
+    }
+
+    /// Returns the result of rotating each vector in `vecs`. See
+    /// `rotate_all_mut`.
+    pub fn rotate_all(&self, vecs: &[Vec3<T>]) -> ~[Vec3<T>] {
+        let mut result = vecs.to_owned();
+        self.rotate_all_mut(result);
+        result
+    }
+
     /// The sum of this quaternion and `other`
     #[inline]
     pub fn add_q(&self, other: &Quat<T>) -> Quat<T> {
@@ -137,12 +296,104 @@ impl<T:Clone + Float> Quat<T> {
                   self.s * other.v.z + self.v.z * other.s + self.v.x * other.v.y - self.v.y * other.v.x)
     }
 
+    /// The negation of the quaternion, which represents the same rotation
+    #[inline]
+    pub fn negate(&self) -> Quat<T> {
+        Quat::from_sv(-self.s, -self.v)
+    }
+
+    /// The rate of change of the quaternion when rotating with the
+    /// world-space `angular_velocity`, in radians per unit of time:
+    /// `q' = 0.5 * (0, w) * q`
+    ///
+    /// The result is not a rotation itself, and is intended to be integrated
+    /// over a timestep by the caller.
+    #[inline]
+    pub fn derivative(&self, angular_velocity: &Vec3<T>) -> Quat<T> {
+        Quat::from_sv(zero!(T), angular_velocity.clone()).mul_q(self).div_s(two!(T))
+    }
+
+    /// Turns the orientation so that its forward direction, the negative `z`
+    /// axis, moves towards `target_forward` by at most `max_radians`.
+    ///
+    /// The turn is the shortest arc between the current and target forward
+    /// directions, so no more roll is introduced than is needed. If the
+    /// target is directly behind, the orientation turns about its local `y`
+    /// axis.
+    pub fn rotate_towards(&self, target_forward: &Vec3<T>, max_radians: T) -> Quat<T> {
+        let forward = self.mul_v(&-Vec3::unit_z::<T>());
+        let target = target_forward.normalize();
+
+        let mut axis = forward.cross(&target);
+        let ahead = forward.dot(&target) > zero!(T);
+        if ahead && axis == Vec3::zero() { return self.clone(); }
+
+        // only a target almost exactly behind leaves the axis undetermined
+        let epsilon = ApproxEq::approx_epsilon::<T,T>();
+        if !ahead && axis.magnitude2() <= epsilon * epsilon {
+            axis = self.mul_v(&Vec3::unit_y());
+        }
+
+        let half = forward.angle(&target).min(&max_radians) / two!(T);
+        Quat::from_sv(half.cos(), axis.normalize().mul_s(half.sin())).mul_q(self)
+    }
+
     /// The dot product of the quaternion and `other`
     #[inline]
     pub fn dot(&self, other: &Quat<T>) -> T {
         self.s * other.s + self.v.dot(&other.v)
     }
 
+    /// The dot product of the quaternion and `other`, clamped to `[-1, 1]` so
+    /// that it stays within the domain of `acos` despite rounding error.
+    #[inline]
+    pub fn dot_clamped(&self, other: &Quat<T>) -> T {
+        self.dot(other).clamp(&-one!(T), &one!(T))
+    }
+
+    /// The angle between the quaternion and `other` on the unit 4-sphere,
+    /// taking into account that `q` and `-q` represent the same rotation.
+    ///
+    /// This is a metric on rotations, lying in the range `[0, pi/2]`. The
+    /// angle of the rotation taking one quaternion to the other is twice this
+    /// distance. Both quaternions should be normalized.
+    #[inline]
+    pub fn geodesic_distance(&self, other: &Quat<T>) -> T {
+        self.dot_clamped(other).abs().acos()
+    }
+
+    /// The angle of the smallest rotation taking the quaternion to `other`,
+    /// in radians in the range `[0, pi]`. Both quaternions should be
+    /// normalized.
+    #[inline]
+    pub fn angle_to(&self, other: &Quat<T>) -> T {
+        self.geodesic_distance(other) * two!(T)
+    }
+
+    /// Returns `true` if the rotations differ by an angle of at most
+    /// `degrees`, given in degrees rather than radians.
+    #[inline]
+    pub fn within_degrees(&self, other: &Quat<T>, degrees: T) -> bool {
+        self.angle_to(other) <= degrees.to_radians()
+    }
+
+    /// Compares the quaternion with `other` using a total order, for
+    /// deterministic sorting and deduplication.
+    ///
+    /// The components are compared lexicographically in the order `w`, `x`,
+    /// `y`, `z`. Unlike `<`, `-0.0` is ordered before `0.0`, and `NaN`s are
+    /// ordered after all other values and are equal to each other.
+    pub fn total_cmp(&self, other: &Quat<T>) -> Ordering {
+        let mut i = 0;
+        while i < 4 {
+            match total_cmp_scalar(self.i(i), other.i(i)) {
+                Equal => i += 1,
+                ord => return ord,
+            }
+        }
+        Equal
+    }
+
     /// The conjugate of the quaternion
     #[inline]
     pub fn conjugate(&self) -> Quat<T> {
@@ -181,6 +432,61 @@ impl<T:Clone + Float> Quat<T> {
         self.mul_s(one!(T) / self.magnitude())
     }
 
+    /// The normalized quaternion, or `None` if its magnitude is too close to
+    /// zero to be normalized.
+    #[inline]
+    pub fn try_normalize(&self) -> Option<Quat<T>> {
+        let magnitude = self.magnitude();
+        if magnitude.approx_eq(&zero!(T)) {
+            None
+        } else {
+            Some(self.div_s(magnitude))
+        }
+    }
+
+    /// The unit axis that the quaternion rotates about. The identity rotation
+    /// has no well defined axis, so the `x` axis is returned for it.
+    pub fn axis(&self) -> Vec3<T> {
+        if self.v == Vec3::zero() {
+            Vec3::unit_x()
+        } else {
+            self.v.normalize()
+        }
+    }
+
+    /// The angle that the quaternion rotates by about its `axis`, in radians
+    /// in the range `[0, 2pi]`.
+    #[inline]
+    pub fn angle(&self) -> T {
+        self.normalize().s.clamp(&-one!(T), &one!(T)).acos() * two!(T)
+    }
+
+    /// Returns `true` if the quaternion represents no rotation, to within
+    /// `epsilon`. Both the identity and its negation are accepted, as they
+    /// describe the same rotation.
+    #[inline]
+    pub fn is_identity(&self, epsilon: &T) -> bool {
+        self.s.abs().approx_eq_eps(&one!(T), epsilon) &&
+        self.v.approx_eq_eps(&Vec3::zero(), epsilon)
+    }
+
+    /// Normalises the quaternion in place
+    #[inline]
+    pub fn normalize_self(&mut self) {
+        let rlen = self.magnitude().recip();
+        self.s = self.s * rlen;
+        self.v.mul_self_s(rlen);
+    }
+
+    /// Renormalizes the quaternion and clamps its scalar component to
+    /// `[-1, 1]`, guarding against accumulated rounding error pushing
+    /// subsequent inverse trigonometric functions out of their domain.
+    #[inline]
+    pub fn sanitize(&self) -> Quat<T> {
+        let q = self.normalize();
+        Quat::from_sv(q.s.clamp(&-one!(T), &one!(T)), q.v)
+    }
+
     /// Normalised linear interpolation
     ///
     /// # Return value
@@ -190,6 +496,25 @@ impl<T:Clone + Float> Quat<T> {
         self.mul_s(one!(T) - amount).add_q(&other.mul_s(amount)).normalize()
     }
 
+    /// Blends several rotations according to their weights.
+    ///
+    /// Each quaternion is negated if needed to lie in the same hemisphere as
+    /// the first, so that the blend takes the short way around. The weighted
+    /// sum is then normalized. This is a cheap approximation to a true
+    /// spherical average, which is accurate for rotations that are close
+    /// together. An empty slice results in the identity rotation.
+    pub fn weighted_average(quats: &[(T, Quat<T>)]) -> Quat<T> {
+        if quats.is_empty() { return Quat::identity(); }
+
+        let (_, ref first) = quats[0];
+        let mut sum = Quat::zero();
+        for quats.iter().advance |&(ref weight, ref q)| {
+            let aligned = if first.dot(q) < zero!(T) { -*q } else { q.clone() };
+            sum = sum.add_q(&aligned.mul_s(weight.clone()));
+        }
+        sum.normalize()
+    }
+
     /// Spherical Linear Intoperlation
     ///
     /// Perform a spherical linear interpolation between the quaternion and
@@ -212,28 +537,70 @@ impl<T:Clone + Float> Quat<T> {
     pub fn slerp(&self, other: &Quat<T>, amount: T) -> Quat<T> {
         use std::num::cast;
 
-        let dot = self.dot(other);
+        let dot = self.dot_clamped(other);
         let dot_threshold = cast(0.9995);
 
         // if quaternions are close together use `nlerp`
         if dot > dot_threshold {
             self.nlerp(other, amount)
         } else {
-            // stay within the domain of acos()
-            let robust_dot = dot.clamp(&-one!(T), &one!(T));
-
-            let theta_0 = robust_dot.acos();    // the angle between the quaternions
+            let theta_0 = dot.acos();           // the angle between the quaternions
             let theta = theta_0 * amount;       // the fraction of theta specified by `amount`
 
-            let q = other.sub_q(&self.mul_s(robust_dot))
+            let q = other.sub_q(&self.mul_s(dot))
                          .normalize();
 
             self.mul_s(theta.cos())
                 .add_q(&q.mul_s(theta.sin()))
         }
     }
+
+    /// Spherical linear interpolation that stays on the hemisphere of `self`
+    ///
+    /// Unlike `slerp`, `other` is first negated if it lies on the opposite
+    /// hemisphere to `self`. Since `q` and `-q` represent the same rotation,
+    /// this always takes the shorter path between the two rotations, and the
+    /// result never flips sign relative to `self`.
+    #[inline]
+    pub fn slerp_nearest(&self, other: &Quat<T>, amount: T) -> Quat<T> {
+        if self.dot(other) < zero!(T) {
+            self.slerp(&-*other, amount)
+        } else {
+            self.slerp(other, amount)
+        }
+    }
+
+    /// Spherically interpolates the quaternion towards `other` in place
+    ///
+    /// See `slerp` for details.
+    #[inline]
+    pub fn slerp_self(&mut self, other: &Quat<T>, amount: T) {
+        *self = self.slerp(other, amount);
+    }
+
+    /// Spherically interpolates towards `other` like `slerp`, and also returns
+    /// the constant world-space angular velocity of the interpolation when it
+    /// takes `duration` units of time to go from `self` to `other`.
+    ///
+    /// The angular velocity is the axis of the rotation from `self` to `other`
+    /// scaled by its angle divided by `duration`, in radians per unit of time,
+    /// as accepted by `derivative`. Both quaternions should be of unit length.
+    pub fn slerp_with_velocity(&self, other: &Quat<T>, amount: T, duration: T) -> (Quat<T>, Vec3<T>) {
+        let delta = other.mul_q(&self.conjugate());
+        let velocity = delta.axis().mul_s(delta.angle() / duration);
+        (self.slerp(other, amount), velocity)
+    }
+}
+
+#[cfg(debug)]
+fn check_basis_columns<T:Clone + Float>(m: &Mat3<T>) {
+    assert!(m.is_rotation(&ApproxEq::approx_epsilon::<T,Quat<T>>()),
+            "the matrix columns must be an orthonormal basis");
 }
 
+#[cfg(not(debug))]
+fn check_basis_columns<T>(_: &Mat3<T>) {}
+
 impl<T:Clone + Num> ToMat3<T> for Quat<T> {
     /// Convert the quaternion to a 3 x 3 rotation matrix
     pub fn to_mat3(&self) -> Mat3<T> {
@@ -261,9 +628,551 @@ impl<T:Clone + Num> ToMat3<T> for Quat<T> {
     }
 }
 
+impl<T:Clone> ToVec4<T> for Quat<T> {
+    /// Converts the quaternion to a four-dimensional vector with the
+    /// components in the order `[x, y, z, w]`, where `w` is the scalar
+    /// component. This matches the layout expected by most graphics APIs and
+    /// shading languages, but differs from the `[w, x, y, z]` order used by
+    /// `Quat::new` and by indexing the quaternion.
+    #[inline]
+    pub fn to_vec4(&self) -> Vec4<T> {
+        Vec4::new(self.v.x.clone(),
+                  self.v.y.clone(),
+                  self.v.z.clone(),
+                  self.s.clone())
+    }
+}
+
 impl<T:Clone + Float> Neg<Quat<T>> for Quat<T> {
     #[inline]
     pub fn neg(&self) -> Quat<T> {
-        Quat::from_sv(-self.s, -self.v)
+        self.negate()
+    }
+}
+
+/// A total order over floating point values, as used by `Quat::total_cmp`
+fn total_cmp_scalar<T:Float>(a: &T, b: &T) -> Ordering {
+    match (a.is_NaN(), b.is_NaN()) {
+        (true, true) => Equal,
+        (true, false) => Greater,
+        (false, true) => Less,
+        (false, false) => {
+            if *a < *b {
+                Less
+            } else if *a > *b {
+                Greater
+            } else {
+                // the values can only differ in the sign of zero
+                match (a.is_negative(), b.is_negative()) {
+                    (true, false) => Less,
+                    (false, true) => Greater,
+                    _ => Equal,
+                }
+            }
+        }
+    }
+}
+
+/// Normalizes each quaternion in `quats` in place, for example to correct
+/// the drift accumulated by a skeleton's joint rotations each frame.
+pub fn normalize_all<T:Clone + Float>(quats: &mut [Quat<T>]) {
+    for quats.mut_iter().advance |q| {
+        q.normalize_self();
+    }
+}
+
+/// Samples a track of `(time, rotation)` keyframes at `time`, spherically
+/// interpolating between the keyframes on either side of it.
+///
+/// The keyframes must be sorted by time. Outside of the time range of the
+/// track the first or last rotation is returned, and an empty track yields
+/// the identity rotation.
+pub fn slerp_track<T:Clone + Float>(keys: &[(T, Quat<T>)], time: T) -> Quat<T> {
+    do sample_track(keys, time) |q0, q1, amount| { q0.slerp(q1, amount) }
+}
+
+/// Samples a track of `(time, rotation)` keyframes at `time`, like
+/// `slerp_track`, but using the cheaper `nlerp` between the keyframes.
+///
+/// The interpolated rotation does not move at a constant angular velocity
+/// between the keyframes, with the error growing with the angle between them.
+/// For densely sampled tracks this is rarely noticeable. Each keyframe is
+/// negated if needed to lie on the same hemisphere as the previous one, so
+/// that the interpolation always takes the shorter path.
+pub fn nlerp_track<T:Clone + Float>(keys: &[(T, Quat<T>)], time: T) -> Quat<T> {
+    do sample_track(keys, time) |q0, q1, amount| {
+        if q0.dot(q1) < zero!(T) {
+            q0.nlerp(&-*q1, amount)
+        } else {
+            q0.nlerp(q1, amount)
+        }
+    }
+}
+
+/// Finds the keyframes on either side of `time`, and interpolates between
+/// them using `interpolate`
+fn sample_track<T:Clone + Float>(keys: &[(T, Quat<T>)], time: T,
+                                 interpolate: &fn(&Quat<T>, &Quat<T>, T) -> Quat<T>) -> Quat<T> {
+    if keys.is_empty() { return Quat::identity(); }
+
+    let (ref first_time, ref first) = keys[0];
+    if time <= *first_time { return first.clone(); }
+
+    let mut i = 1;
+    while i < keys.len() {
+        let (ref t1, ref q1) = keys[i];
+        if time <= *t1 {
+            let (ref t0, ref q0) = keys[i - 1];
+            return interpolate(q0, q1, (time - *t0) / (*t1 - *t0));
+        }
+        i += 1;
+    }
+
+    let (_, ref last) = keys[keys.len() - 1];
+    last.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use math::BitEq;
+    use math::mat::*;
+    use math::quat::*;
+    use math::vec::*;
+
+    #[test]
+    fn test_normalize_self() {
+        let q = Quat::new::<float>(1.0, 2.0, 3.0, 4.0);
+
+        let mut mut_q = q;
+        mut_q.normalize_self();
+        assert_eq!(mut_q, q.normalize());
+        assert!(mut_q.magnitude().approx_eq(&1.0));
+    }
+
+    #[test]
+    fn test_from_mat4() {
+        // a quarter turn about the z axis
+        let q = Quat::new::<float>(0.5f.sqrt(), 0.0, 0.0, 0.5f.sqrt());
+
+        let mut m = q.to_mat3().to_mat4();
+        *m.mut_c(3) = Vec4::new(1.0, 2.0, 3.0, 1.0);
+
+        assert_approx_eq!(Quat::from_mat4(&m), q);
+    }
+
+    #[test]
+    fn test_slerp_track() {
+        // quarter turns about the z axis
+        let a = Quat::identity::<float>();
+        let b = Quat::new::<float>((Real::frac_pi_4::<float>()).cos(), 0.0, 0.0, (Real::frac_pi_4::<float>()).sin());
+        let c = Quat::new::<float>(0.0, 0.0, 0.0, 1.0);
+        let keys = [(0.0, a), (1.0, b), (3.0, c)];
+
+        assert_eq!(slerp_track(keys, -1.0), a);
+        assert_approx_eq!(slerp_track(keys, 1.0), b);
+        assert_approx_eq!(slerp_track(keys, 0.5), a.slerp(&b, 0.5));
+        assert_approx_eq!(slerp_track(keys, 2.5), b.slerp(&c, 0.75));
+        assert_eq!(slerp_track(keys, 4.0), c);
+        assert_eq!(slerp_track([], 1.0), Quat::identity::<float>());
+    }
+
+    #[test]
+    fn test_approx_epsilon() {
+        assert_eq!(ApproxEq::approx_epsilon::<float, Quat<float>>(), 1.0e-5);
+
+        // differences too large for the scalar epsilon are still within the
+        // quaternion's tolerance
+        let a = Quat::new::<float>(1.0, 0.0, 0.0, 0.0);
+        let b = Quat::new::<float>(1.0 - 5.0e-6, 5.0e-6, 0.0, 0.0);
+        assert!(!a.s.approx_eq(&b.s));
+        assert!(a.approx_eq(&b));
+        assert!(!a.approx_eq(&Quat::new::<float>(1.0 - 5.0e-5, 0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_slerp_self() {
+        let a = Quat::identity::<float>();
+        let b = Quat::new::<float>(0.0, 0.0, 1.0, 0.0);
+
+        let mut mut_a = a;
+        mut_a.slerp_self(&b, 0.3);
+        assert_eq!(mut_a, a.slerp(&b, 0.3));
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let q = Quat::new::<float>(1.0 + 1.0e-7, 0.0, 0.0, 0.0);
+        assert!(q.s.acos().is_NaN());
+
+        let sanitized = q.sanitize();
+        assert!(!sanitized.s.acos().is_NaN());
+        assert!(sanitized.s <= 1.0);
+        assert_approx_eq!(sanitized, Quat::identity::<float>());
+    }
+
+    #[test]
+    fn test_dot_clamped() {
+        let a = Quat::new::<float>(1.0 + 1.0e-7, 0.0, 0.0, 0.0);
+        let b = Quat::new::<float>(1.0, 1.0e-8, 0.0, 0.0);
+
+        assert!(a.dot(&b) > 1.0);
+        assert_eq!(a.dot_clamped(&b), 1.0);
+        assert_eq!(a.dot_clamped(&-b), -1.0);
+        assert!(!a.dot_clamped(&b).acos().is_NaN());
+    }
+
+    #[test]
+    fn test_look_at() {
+        let up = Vec3::new::<float>(0.0, 1.0, 0.0);
+        let forward = Vec3::new::<float>(0.0, 0.0, 1.0);
+        let back = Vec3::new::<float>(0.0, 0.0, -1.0);
+
+        assert_approx_eq!(Quat::look_at_lh(&forward, &up), Quat::identity());
+        assert_approx_eq!(Quat::look_at_rh(&back, &up), Quat::identity());
+
+        // the rotation maps the local `z` axis onto the handedness' forward
+        // direction
+        let dir = Vec3::new::<float>(1.0, 0.0, 1.0).normalize();
+        assert_approx_eq!(Quat::look_at_lh(&dir, &up).mul_v(&forward), dir);
+        let dir = Vec3::new::<float>(1.0, 0.0, -1.0).normalize();
+        assert_approx_eq!(Quat::look_at_rh(&dir, &up).mul_v(&back), dir);
+    }
+
+    #[test]
+    fn test_from_yaw_pitch() {
+        let forward = Vec3::new::<float>(0.0, 0.0, -1.0);
+        let half_sqrt2 = 0.5f.sqrt();
+
+        assert_approx_eq!(Quat::from_yaw_pitch::<float>(0.0, 0.0).mul_v(&forward), forward);
+        assert_approx_eq!(Quat::from_yaw_pitch::<float>(Real::frac_pi_2(), 0.0).mul_v(&forward),
+                          Vec3::new::<float>(-1.0, 0.0, 0.0));
+        assert_approx_eq!(Quat::from_yaw_pitch::<float>(0.0, Real::frac_pi_4()).mul_v(&forward),
+                          Vec3::new::<float>(0.0, half_sqrt2, -half_sqrt2));
+        assert_approx_eq!(Quat::from_yaw_pitch::<float>(Real::pi(), -Real::frac_pi_4::<float>()).mul_v(&forward),
+                          Vec3::new::<float>(0.0, -half_sqrt2, half_sqrt2));
+
+        // the pitch is clamped just short of the pole, so looking straight up
+        // still leaves a forward direction that is not parallel to `y`
+        let up = Quat::from_yaw_pitch::<float>(0.0, Real::frac_pi_2()).mul_v(&forward);
+        assert!(up.y < 1.0);
+        assert!(up.z < 0.0);
+        assert_eq!(Quat::from_yaw_pitch::<float>(0.0, Real::pi()).mul_v(&forward), up);
+    }
+
+    #[test]
+    fn test_bit_eq() {
+        let q = Quat::new::<float>(1.0, 0.0, 0.0, 0.0);
+        assert!(q.bit_eq(&q));
+        assert!(!q.bit_eq(&Quat::new::<float>(1.0, -0.0, 0.0, 0.0)));
+        assert!(!q.bit_eq(&Quat::new::<float>(1.0, 0.0, 0.0, 1.0e-300)));
+    }
+
+    #[test]
+    fn test_from_mat3_unscaled() {
+        // a rotation of 0.5 radians about the z axis
+        let q = Quat::new::<float>(0.25f.cos(), 0.0, 0.0, 0.25f.sin());
+        let r = q.to_mat3();
+        let m = Mat3::from_axes(r.x.mul_s(2.0), r.y.mul_s(3.0), r.z.mul_s(0.5));
+
+        let (rot, scale) = Quat::from_mat3_unscaled(&m);
+        assert_approx_eq!(rot, q);
+        assert_approx_eq!(scale, Vec3::new::<float>(2.0, 3.0, 0.5));
+    }
+
+    #[test]
+    fn test_weighted_average() {
+        let a = Quat::identity::<float>();
+        let b = Quat::new::<float>(0.0, 0.0, 0.0, 1.0);
+
+        assert_approx_eq!(Quat::weighted_average([(0.5, a), (0.5, b)]), a.slerp(&b, 0.5));
+        assert_approx_eq!(Quat::weighted_average([(1.0, a), (1.0, -b)]), a.slerp(&b, 0.5));
+        assert_approx_eq!(Quat::weighted_average([(2.0, b)]), b);
+        assert_eq!(Quat::weighted_average::<float>([]), Quat::identity());
+    }
+
+    #[test]
+    fn test_derivative() {
+        // a rotation about the z axis at 2 radians per unit of time
+        let omega = Vec3::new::<float>(0.0, 0.0, 2.0);
+        let at = |t: float| Quat::new::<float>(t.cos(), 0.0, 0.0, t.sin());
+
+        let t = 0.3;
+        let h = 1.0e-5;
+        let finite = at(t + h).sub_q(&at(t - h)).div_s(2.0 * h);
+        assert!(at(t).derivative(&omega).approx_eq_eps(&finite, &1.0e-6));
+    }
+
+    #[test]
+    fn test_slerp_nearest() {
+        let a = Quat::identity::<float>();
+        // a rotation of 0.5 radians about the z axis, on the far hemisphere
+        let b = -Quat::new::<float>(0.25f.cos(), 0.0, 0.0, 0.25f.sin());
+        assert!(a.dot(&b) < 0.0);
+
+        assert_approx_eq!(a.slerp_nearest(&b, 0.5), a.slerp(&-b, 0.5));
+        assert_approx_eq!(a.slerp_nearest(&b, 0.5), Quat::new::<float>(0.125f.cos(), 0.0, 0.0, 0.125f.sin()));
+        assert!(!a.slerp(&b, 0.5).approx_eq(&a.slerp_nearest(&b, 0.5)));
+        assert!(a.slerp_nearest(&b, 1.0).s > 0.0);
+    }
+
+    #[test]
+    fn test_to_vec4() {
+        let q = Quat::new::<float>(1.0, 2.0, 3.0, 4.0);
+
+        assert_eq!(q.to_vec4(), Vec4::new::<float>(2.0, 3.0, 4.0, 1.0));
+        assert_eq!(Quat::from_vec4(q.to_vec4()), q);
+    }
+
+    #[test]
+    fn test_geodesic_distance() {
+        let a = Quat::identity::<float>();
+        // a rotation of 0.5 radians about the z axis
+        let b = Quat::new::<float>(0.25f.cos(), 0.0, 0.0, 0.25f.sin());
+
+        assert_approx_eq!(a.geodesic_distance(&b), 0.25);
+        assert_approx_eq!(a.geodesic_distance(&-b), 0.25);
+        assert_approx_eq!(b.geodesic_distance(&b), 0.0);
+
+        let quats = [a, b,
+                     Quat::new::<float>(0.5f.cos(), 0.5f.sin(), 0.0, 0.0),
+                     Quat::new::<float>(0.5, 0.5, -0.5, 0.5),
+                     Quat::new::<float>(0.0, 0.0, 1.0, 0.0)];
+        for quats.iter().advance |p| {
+            for quats.iter().advance |q| {
+                for quats.iter().advance |r| {
+                    assert!(p.geodesic_distance(r) <= p.geodesic_distance(q) + q.geodesic_distance(r) + 1.0e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_total_cmp() {
+        use std::cmp::{Less, Equal, Greater};
+
+        let nan = Quat::new::<float>(Float::NaN(), 0.0, 0.0, 0.0);
+        let pos = Quat::new::<float>(1.0, 0.0, 0.0, 0.0);
+        let neg = Quat::new::<float>(1.0, -0.0, 0.0, 0.0);
+        let small = Quat::new::<float>(0.5, 1.0, 0.0, 0.0);
+
+        assert_eq!(pos.total_cmp(&pos), Equal);
+        assert_eq!(neg.total_cmp(&pos), Less);
+        assert_eq!(small.total_cmp(&pos), Less);
+        assert_eq!(nan.total_cmp(&pos), Greater);
+        assert_eq!(nan.total_cmp(&nan), Equal);
+
+        let mut quats = ~[nan, pos, small, neg, small];
+        let mut i = 1;
+        while i < quats.len() {
+            let mut j = i;
+            while j > 0 && quats[j - 1].total_cmp(&quats[j]) == Greater {
+                quats.swap(j - 1, j);
+                j -= 1;
+            }
+            i += 1;
+        }
+        let expected = [small, small, neg, pos, nan];
+        for quats.iter().zip(expected.iter()).advance |(a, b)| {
+            assert!(a.bit_eq(b));
+        }
+    }
+
+    #[test]
+    fn test_rotate_all() {
+        let q = Quat::new::<float>(0.5, 0.5, -0.5, 0.5);
+        let vecs = [Vec3::new::<float>(1.0, 0.0, 0.0),
+                    Vec3::new::<float>(0.0, 2.0, 0.0),
+                    Vec3::new::<float>(1.0, -2.0, 3.0)];
+
+        let rotated = q.rotate_all(vecs);
+        assert_eq!(rotated.len(), vecs.len());
+        for vecs.iter().zip(rotated.iter()).advance |(v, r)| {
+            assert_approx_eq!(*r, q.mul_v(v));
+        }
+
+        let mut mut_vecs = vecs;
+        q.rotate_all_mut(mut_vecs);
+        for vecs.iter().zip(mut_vecs.iter()).advance |(v, r)| {
+            assert_approx_eq!(*r, q.mul_v(v));
+        }
+    }
+
+    #[test]
+    fn test_rotation_xyz() {
+        let x = Vec3::unit_x::<float>();
+        let y = Vec3::unit_y::<float>();
+        let z = Vec3::unit_z::<float>();
+        let quarter = Real::frac_pi_2::<float>();
+
+        assert_approx_eq!(Quat::rotation_x(quarter).mul_v(&y), z);
+        assert_approx_eq!(Quat::rotation_x(quarter).mul_v(&x), x);
+        assert_approx_eq!(Quat::rotation_y(quarter).mul_v(&z), x);
+        assert_approx_eq!(Quat::rotation_y(quarter).mul_v(&y), y);
+        assert_approx_eq!(Quat::rotation_z(quarter).mul_v(&x), y);
+        assert_approx_eq!(Quat::rotation_z(quarter).mul_v(&z), z);
+    }
+
+    #[test]
+    fn test_nlerp_track() {
+        let a = Quat::identity::<float>();
+        let b = Quat::rotation_z::<float>(Real::frac_pi_2());
+        let c = Quat::rotation_z::<float>(Real::pi());
+        // the same rotation as `c`, on the opposite hemisphere
+        let keys = [(0.0, a), (1.0, b), (2.0, -c)];
+
+        assert_eq!(nlerp_track(keys, -1.0), a);
+        assert_approx_eq!(nlerp_track(keys, 1.0), b);
+        assert_approx_eq!(nlerp_track(keys, 0.5), a.nlerp(&b, 0.5));
+        assert_approx_eq!(nlerp_track(keys, 0.5), slerp_track(keys, 0.5));
+        assert_approx_eq!(nlerp_track(keys, 1.5), b.nlerp(&c, 0.5));
+        assert_eq!(nlerp_track(keys, 3.0), -c);
+        assert_eq!(nlerp_track([], 1.0), Quat::identity::<float>());
+    }
+
+    #[test]
+    fn test_rotate_towards() {
+        let q = Quat::identity::<float>();
+        let left = Vec3::new::<float>(-1.0, 0.0, 0.0);
+
+        // within the limit, the target is reached
+        let turned = q.rotate_towards(&left, Real::pi());
+        assert_approx_eq!(turned, Quat::rotation_y(Real::frac_pi_2()));
+        assert_approx_eq!(turned.mul_v(&-Vec3::unit_z::<float>()), left);
+
+        // clamped by the limit
+        let turned = q.rotate_towards(&left, Real::frac_pi_4());
+        assert_approx_eq!(turned, Quat::rotation_y(Real::frac_pi_4()));
+
+        // already facing the target
+        assert_eq!(q.rotate_towards(&Vec3::new(0.0, 0.0, -2.0), 1.0), q);
+
+        // a target a few milliradians away is still turned towards
+        let near = Quat::rotation_y::<float>(0.003).mul_v(&-Vec3::unit_z::<float>());
+        assert_approx_eq!(q.rotate_towards(&near, Real::pi()), Quat::rotation_y(0.003));
+        assert_approx_eq!(q.rotate_towards(&near, 0.001), Quat::rotation_y(0.001));
+    }
+
+    #[test]
+    fn test_try_normalize() {
+        assert_eq!(Quat::zero::<float>().try_normalize(), None);
+        assert_eq!(Quat::new::<float>(0.0, 0.0, 3.0, 4.0).try_normalize(),
+                   Some(Quat::new::<float>(0.0, 0.0, 0.6, 0.8)));
+    }
+
+    #[test]
+    fn test_is_identity() {
+        let q = Quat::identity::<float>();
+        assert!(q.is_identity(&1.0e-6));
+        assert!((-q).is_identity(&1.0e-6));
+        assert!(!Quat::rotation_x::<float>(0.01).is_identity(&1.0e-6));
+        assert!(Quat::rotation_x::<float>(1.0e-7).is_identity(&1.0e-6));
+    }
+
+    #[test]
+    fn test_negate() {
+        let q = Quat::new::<float>(1.0, -2.0, 3.0, -4.0);
+        assert_eq!(q.negate(), Quat::new::<float>(-1.0, 2.0, -3.0, 4.0));
+        assert_eq!(q.negate(), -q);
+    }
+
+    #[test]
+    fn test_slerp_with_velocity() {
+        let a = Quat::rotation_x::<float>(0.3);
+        let b = Quat::rotation_y::<float>(1.2).mul_q(&a);
+        let duration = 4.0;
+
+        let (q, w) = a.slerp_with_velocity(&b, 0.25, duration);
+        assert_eq!(q, a.slerp(&b, 0.25));
+        assert_approx_eq!(w, Vec3::new::<float>(0.0, 0.3, 0.0));
+
+        // rotating at `w` for the whole duration reaches the end point
+        let half = w.magnitude() * duration / 2.0;
+        let step = Quat::from_sv(half.cos(), w.normalize().mul_s(half.sin()));
+        assert_approx_eq!(step.mul_q(&a), b);
+    }
+
+    #[test]
+    fn test_from_basis_columns() {
+        let (c, s) = (0.5f.cos(), 0.5f.sin());
+        let m = Mat3::new::<float>( c,   s,   0.0,
+                                   -s,   c,   0.0,
+                                   0.0, 0.0, 1.0);
+
+        assert_approx_eq!(Quat::from_basis_columns(&m), Quat::rotation_z(0.5));
+        assert_approx_eq!(Quat::from_basis_columns(&m.transpose()), Quat::rotation_z(-0.5));
+    }
+
+    #[test]
+    fn test_from_basis_columns_half_turn() {
+        assert_eq!(Quat::from_basis_columns(&Mat3::new::<float>(1.0,  0.0,  0.0,
+                                                                0.0, -1.0,  0.0,
+                                                                0.0,  0.0, -1.0)),
+                   Quat::new::<float>(0.0, 1.0, 0.0, 0.0));
+        assert_eq!(Quat::from_basis_columns(&Mat3::new::<float>(-1.0, 0.0,  0.0,
+                                                                 0.0, 1.0,  0.0,
+                                                                 0.0, 0.0, -1.0)),
+                   Quat::new::<float>(0.0, 0.0, 1.0, 0.0));
+        assert_eq!(Quat::from_basis_columns(&Mat3::new::<float>(-1.0,  0.0, 0.0,
+                                                                 0.0, -1.0, 0.0,
+                                                                 0.0,  0.0, 1.0)),
+                   Quat::new::<float>(0.0, 0.0, 0.0, 1.0));
+
+        // negative trace, so each of these takes a non-trace branch
+        for [Quat::rotation_x::<float>(3.0),
+             Quat::rotation_y::<float>(3.0),
+             Quat::rotation_z::<float>(3.0)].iter().advance |q| {
+            assert_approx_eq!(Quat::from_basis_columns(&q.to_mat3()), *q);
+        }
+    }
+
+    #[test]
+    #[should_fail]
+    #[cfg(debug)]
+    fn test_from_basis_columns_not_rotation() {
+        Quat::from_basis_columns(&Mat3::from_value::<float>(2.0));
+    }
+
+    #[test]
+    fn test_within_degrees() {
+        let a = Quat::rotation_y::<float>(0.3);
+        let b = Quat::rotation_y::<float>(0.3 + 5f.to_radians());
+
+        assert_approx_eq!(a.angle_to(&b), 5f.to_radians());
+        assert_approx_eq!(a.angle_to(&-b), 5f.to_radians());
+        assert!(a.within_degrees(&b, 10.0));
+        assert!(!a.within_degrees(&b, 1.0));
+    }
+
+    #[test]
+    fn test_from_angle_axis() {
+        let axis = Vec3::new::<float>(1.0, 2.0, 2.0).normalize();
+
+        assert_approx_eq!(Quat::from_angle_axis_degrees(90.0, &axis),
+                          Quat::from_angle_axis(Real::frac_pi_2(), &axis));
+        assert_approx_eq!(Quat::from_angle_axis::<float>(0.5, &Vec3::unit_z()),
+                          Quat::rotation_z(0.5));
+        assert_approx_eq!(Quat::from_angle_axis_degrees::<float>(-30.0, &Vec3::unit_x()),
+                          Quat::rotation_x((-30f).to_radians()));
+    }
+
+    #[test]
+    fn test_normalize_all() {
+        let mut quats = [Quat::new::<float>(2.0, 0.0, 0.0, 0.0),
+                         Quat::new::<float>(1.0, 1.0, 1.0, 1.0),
+                         Quat::rotation_y::<float>(0.7).mul_s(0.25)];
+        normalize_all(quats);
+
+        for quats.iter().advance |q| {
+            assert_approx_eq!(q.magnitude(), 1.0);
+        }
+        assert_eq!(quats[0], Quat::identity());
+        assert_approx_eq!(quats[2], Quat::rotation_y(0.7));
+    }
+
+    #[test]
+    fn test_to_array() {
+        let q = Quat::new::<float>(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(q.to_array_xyzw(), [2.0, 3.0, 4.0, 1.0]);
+        assert_eq!(q.to_array_wxyz(), [1.0, 2.0, 3.0, 4.0]);
     }
 }
@@ -15,8 +15,12 @@
 
 //! Ray types
 
-use math::{Point2, Point3};
-use math::{Vec2, Vec3};
+use std::iterator::Iterator;
+
+use math::Mat4;
+use math::Plane3;
+use math::{Point, Point2, Point3};
+use math::{Vec2, Vec3, ToVec4};
 
 #[deriving(Clone, Eq)]
 pub struct Ray2<T> {
@@ -34,6 +38,23 @@ impl<T> Ray2<T> {
     }
 }
 
+impl<T:Clone + Float> Ray2<T> {
+    /// Creates a ray starting at `origin` with a normalized direction
+    /// pointing towards `through`
+    #[inline]
+    pub fn from_points(origin: &Point2<T>, through: &Point2<T>) -> Ray2<T> {
+        origin.ray_to(through)
+    }
+
+    /// Returns `true` if the rays describe the same half-line to within
+    /// `epsilon`, ignoring the magnitudes of their directions.
+    #[inline]
+    pub fn is_same(&self, other: &Ray2<T>, epsilon: &T) -> bool {
+        self.origin.approx_eq_eps(&other.origin, epsilon) &&
+        self.direction.normalize().approx_eq_eps(&other.direction.normalize(), epsilon)
+    }
+}
+
 #[deriving(Clone, Eq)]
 pub struct Ray3<T> {
     origin: Point3<T>,
@@ -49,3 +70,230 @@ impl<T> Ray3<T> {
         Ray3 { origin: origin, direction: direction }
     }
 }
+
+impl<T:Clone + Float> Ray3<T> {
+    /// Creates a ray starting at `origin` with a normalized direction
+    /// pointing towards `through`
+    #[inline]
+    pub fn from_points(origin: &Point3<T>, through: &Point3<T>) -> Ray3<T> {
+        origin.ray_to(through)
+    }
+
+    /// Returns `true` if the rays describe the same half-line to within
+    /// `epsilon`, ignoring the magnitudes of their directions.
+    #[inline]
+    pub fn is_same(&self, other: &Ray3<T>, epsilon: &T) -> bool {
+        self.origin.approx_eq_eps(&other.origin, epsilon) &&
+        self.direction.normalize().approx_eq_eps(&other.direction.normalize(), epsilon)
+    }
+
+    /// Returns the point at the distance `t` along the ray, measured in
+    /// multiples of the direction vector.
+    #[inline]
+    pub fn point_at(&self, t: T) -> Point3<T> {
+        self.origin + self.direction.mul_s(t)
+    }
+
+    /// Transforms the ray by the homogeneous matrix `m`, treating the origin
+    /// as a point and the direction as a vector.
+    ///
+    /// The direction is not renormalized, so that a distance along the
+    /// transformed ray corresponds to the same point as the distance along
+    /// the original ray.
+    pub fn transform(&self, m: &Mat4<T>) -> Ray3<T> {
+        let origin = m.mul_v(&self.origin.to_vec4());
+        let direction = m.mul_v(&self.direction.to_vec4());
+        Ray3::new(Point3::new(origin.x.clone(), origin.y.clone(), origin.z.clone()),
+                  Vec3::new(direction.x.clone(), direction.y.clone(), direction.z.clone()))
+    }
+
+    /// Computes the parameters of the points of closest approach between the
+    /// lines through `self` and `other`. The parameters are not restricted
+    /// to the forward direction of either ray.
+    ///
+    /// # Return value
+    ///
+    /// A tuple `(s, t)`, such that `self.point_at(s)` and `other.point_at(t)`
+    /// are the closest points. If the rays are parallel, `s` is zero and `t`
+    /// is the parameter of the projection of the origin of `self` onto
+    /// `other`.
+    pub fn closest_parameters(&self, other: &Ray3<T>) -> (T, T) {
+        let r = self.origin - other.origin;
+        let a = self.direction.dot(&self.direction);
+        let b = self.direction.dot(&other.direction);
+        let c = self.direction.dot(&r);
+        let e = other.direction.dot(&other.direction);
+        let f = other.direction.dot(&r);
+        let denom = a * e - b * b;
+
+        if denom <= a * e * ApproxEq::approx_epsilon::<T,T>() {
+            (zero!(T), f / e)
+        } else {
+            ((b * f - c * e) / denom, (a * f - b * c) / denom)
+        }
+    }
+
+    /// Reflects the ray off `plane`.
+    ///
+    /// # Return value
+    ///
+    /// - `Some(r)`: The reflected ray `r`, starting at the point where the
+    ///              ray hits the plane.
+    /// - `None`:    The ray does not hit the plane.
+    pub fn reflect(&self, plane: &Plane3<T>) -> Option<Ray3<T>> {
+        do plane.intersection_r(self).map |hit| {
+            Ray3::new(hit.clone(), self.direction.reflect(&plane.normal.normalize()))
+        }
+    }
+
+    /// Returns the point on the infinite line through the ray that is closest
+    /// to `pos`. Unlike a point on the ray itself, this may lie behind the
+    /// origin.
+    #[inline]
+    pub fn closest_point_on_line(&self, pos: &Point3<T>) -> Point3<T> {
+        let t = (*pos - self.origin).dot(&self.direction) / self.direction.dot(&self.direction);
+        self.point_at(t)
+    }
+
+    /// Mirrors `pos` across the infinite line through the ray, which is the
+    /// same as rotating it half a turn about the line.
+    #[inline]
+    pub fn reflect_point(&self, pos: &Point3<T>) -> Point3<T> {
+        let closest = self.closest_point_on_line(pos);
+        closest + (closest - *pos)
+    }
+
+    /// Returns an iterator over `count` points along the ray, starting at
+    /// the origin and spaced `step` multiples of the direction vector apart.
+    /// The points are `step` apart in space if the direction is normalized.
+    #[inline]
+    pub fn march(&self, step: T, count: uint) -> RayMarchIterator<T> {
+        RayMarchIterator { ray: self.clone(), step: step, index: 0, count: count }
+    }
+}
+
+/// An iterator over evenly spaced points along a ray. See `Ray3::march`.
+pub struct RayMarchIterator<T> {
+    ray: Ray3<T>,
+    step: T,
+    index: uint,
+    count: uint,
+}
+
+impl<T:Clone + Float> Iterator<Point3<T>> for RayMarchIterator<T> {
+    pub fn next(&mut self) -> Option<Point3<T>> {
+        use std::num::cast;
+
+        if self.index < self.count {
+            let t = self.step * cast(self.index);
+            self.index += 1;
+            Some(self.ray.point_at(t))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::mat::*;
+    use math::plane::*;
+    use math::point::*;
+    use math::ray::*;
+    use math::vec::*;
+
+    #[test]
+    fn test_transform() {
+        let ray = Ray3::new(Point3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        let translation = Mat4::new(1.0, 0.0, 0.0, 0.0,
+                                    0.0, 1.0, 0.0, 0.0,
+                                    0.0, 0.0, 1.0, 0.0,
+                                    2.0, 3.0, 4.0, 1.0);
+        assert_eq!(ray.transform(&translation),
+                   Ray3::new(Point3::new(3.0, 3.0, 4.0), Vec3::new(1.0, 0.0, 0.0)));
+
+        // a quarter turn about the z axis
+        let rotation = Mat4::new( 0.0, 1.0, 0.0, 0.0,
+                                 -1.0, 0.0, 0.0, 0.0,
+                                  0.0, 0.0, 1.0, 0.0,
+                                  0.0, 0.0, 0.0, 1.0);
+        assert_eq!(ray.transform(&rotation),
+                   Ray3::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_reflect() {
+        let ground = Plane3::from_abcd(0.0, 1.0, 0.0, 0.0);
+        let ray = Ray3::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+
+        assert_eq!(ray.reflect(&ground),
+                   Some(Ray3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0))));
+        assert_eq!(Ray3::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 0.0)).reflect(&ground), None);
+    }
+
+    #[test]
+    fn test_from_points() {
+        let a = Point2::new(1.0, 1.0);
+        let b = Point2::new(4.0, 5.0);
+        assert_eq!(Ray2::from_points(&a, &b), a.ray_to(&b));
+        assert_approx_eq!(Ray2::from_points(&a, &b), Ray2::new(a, Vec2::new(0.6, 0.8)));
+
+        let a = Point3::new(1.0, 2.0, 3.0);
+        let b = Point3::new(1.0, 2.0, -1.0);
+        assert_eq!(Ray3::from_points(&a, &b), a.ray_to(&b));
+        assert_eq!(Ray3::from_points(&a, &b), Ray3::new(a, Vec3::new(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn test_closest_parameters() {
+        // intersecting
+        let a = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let b = Ray3::new(Point3::new(2.0, -3.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(a.closest_parameters(&b), (2.0, 3.0));
+
+        // parallel
+        let c = Ray3::new(Point3::new(5.0, 1.0, 0.0), Vec3::new(-2.0, 0.0, 0.0));
+        assert_eq!(a.closest_parameters(&c), (0.0, 2.5));
+
+        // skew, with the closest point behind the origin of `other`
+        let d = Ray3::new(Point3::new(-1.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 1.0));
+        let e = Ray3::new(Point3::new(0.0, 2.0, 3.0), Vec3::new(0.0, 1.0, 0.0));
+        let (s, t) = d.closest_parameters(&e);
+        assert_eq!((s, t), (2.0, -2.0));
+        assert_eq!(d.point_at(s).distance(&e.point_at(t)), 1.0);
+    }
+
+    #[test]
+    fn test_march() {
+        let ray = Ray3::new(Point3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+        let points: ~[Point3<float>] = ray.march(0.5, 3).collect();
+
+        assert_eq!(points, ~[Point3::new(1.0, 0.0, 0.0),
+                             Point3::new(1.0, 0.0, -0.5),
+                             Point3::new(1.0, 0.0, -1.0)]);
+        assert_eq!(ray.march(0.5, 0).next(), None);
+    }
+
+    #[test]
+    fn test_is_same() {
+        let a = Ray3::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 3.0, 4.0));
+        let b = Ray3::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 0.6, 0.8));
+        assert!(a != b);
+        assert!(a.is_same(&b, &1.0e-6));
+        assert!(!a.is_same(&Ray3::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, -0.6, -0.8)), &1.0e-6));
+        assert!(!a.is_same(&Ray3::new(Point3::new(1.0, 2.5, 3.0), Vec3::new(0.0, 0.6, 0.8)), &1.0e-6));
+
+        let c = Ray2::new(Point2::new(0.0, 1.0), Vec2::new(-2.0, 0.0));
+        assert!(c.is_same(&Ray2::new(Point2::new(0.0, 1.0), Vec2::new(-1.0, 0.0)), &1.0e-6));
+    }
+
+    #[test]
+    fn test_reflect_point() {
+        let x_axis = Ray3::new(Point3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0));
+
+        assert_eq!(x_axis.closest_point_on_line(&Point3::new(-4.0, 2.0, 3.0)), Point3::new(-4.0, 0.0, 0.0));
+        assert_eq!(x_axis.reflect_point(&Point3::new(-4.0, 2.0, 3.0)), Point3::new(-4.0, -2.0, -3.0));
+        assert_eq!(x_axis.reflect_point(&Point3::new(1.0, 0.0, 0.0)), Point3::new(1.0, 0.0, 0.0));
+    }
+}
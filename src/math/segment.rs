@@ -0,0 +1,242 @@
+// Copyright 2013 The Lmath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Line segment types
+
+use math::{Point, Point2, Point3};
+use math::{Vec2, Vec3};
+
+/// A finite line segment in two dimensions
+#[deriving(Clone, Eq)]
+pub struct Segment2<T> {
+    start: Point2<T>,
+    end: Point2<T>,
+}
+
+impl_approx!(Segment2 { start, end })
+
+impl<T> Segment2<T> {
+    /// Creates a new segment running from `start` to `end`
+    #[inline]
+    pub fn new(start: Point2<T>, end: Point2<T>) -> Segment2<T> {
+        Segment2 { start: start, end: end }
+    }
+}
+
+impl<T:Clone + Float> Segment2<T> {
+    /// Returns the vector running from the start to the end of the segment
+    #[inline]
+    pub fn direction(&self) -> Vec2<T> {
+        self.end - self.start
+    }
+
+    /// Returns the squared length of the segment
+    #[inline]
+    pub fn length2(&self) -> T {
+        self.start.distance2(&self.end)
+    }
+
+    /// Returns the length of the segment
+    #[inline]
+    pub fn length(&self) -> T {
+        self.start.distance(&self.end)
+    }
+
+    /// Returns the point at the parameter `t`, where `0` corresponds to the
+    /// start and `1` to the end of the segment.
+    #[inline]
+    pub fn point_at(&self, t: T) -> Point2<T> {
+        self.start + self.direction().mul_s(t)
+    }
+
+    /// Returns the point on the segment that is closest to `point`
+    pub fn closest_point(&self, point: &Point2<T>) -> Point2<T> {
+        let dir = self.direction();
+        let len2 = dir.magnitude2();
+        if len2 == zero!(T) {
+            self.start.clone()
+        } else {
+            let t = (*point - self.start).dot(&dir) / len2;
+            self.point_at(t.clamp(&zero!(T), &one!(T)))
+        }
+    }
+}
+
+/// A finite line segment in three dimensions
+#[deriving(Clone, Eq)]
+pub struct Segment3<T> {
+    start: Point3<T>,
+    end: Point3<T>,
+}
+
+impl_approx!(Segment3 { start, end })
+
+impl<T> Segment3<T> {
+    /// Creates a new segment running from `start` to `end`
+    #[inline]
+    pub fn new(start: Point3<T>, end: Point3<T>) -> Segment3<T> {
+        Segment3 { start: start, end: end }
+    }
+}
+
+impl<T:Clone + Float> Segment3<T> {
+    /// Returns the vector running from the start to the end of the segment
+    #[inline]
+    pub fn direction(&self) -> Vec3<T> {
+        self.end - self.start
+    }
+
+    /// Returns the squared length of the segment
+    #[inline]
+    pub fn length2(&self) -> T {
+        self.start.distance2(&self.end)
+    }
+
+    /// Returns the length of the segment
+    #[inline]
+    pub fn length(&self) -> T {
+        self.start.distance(&self.end)
+    }
+
+    /// Returns the point at the parameter `t`, where `0` corresponds to the
+    /// start and `1` to the end of the segment.
+    #[inline]
+    pub fn point_at(&self, t: T) -> Point3<T> {
+        self.start + self.direction().mul_s(t)
+    }
+
+    /// Returns the point on the segment that is closest to `point`
+    pub fn closest_point(&self, point: &Point3<T>) -> Point3<T> {
+        let dir = self.direction();
+        let len2 = dir.magnitude2();
+        if len2 == zero!(T) {
+            self.start.clone()
+        } else {
+            let t = (*point - self.start).dot(&dir) / len2;
+            self.point_at(t.clamp(&zero!(T), &one!(T)))
+        }
+    }
+
+    /// Computes the closest pair of points between `self` and `other`.
+    ///
+    /// # Return value
+    ///
+    /// A tuple containing the point on `self` and the point on `other`. When
+    /// the segments are parallel there are many closest pairs, in which case
+    /// one of them is chosen arbitrarily. Segments of zero length are treated
+    /// as points.
+    pub fn closest_points(&self, other: &Segment3<T>) -> (Point3<T>, Point3<T>) {
+        let eps = ApproxEq::approx_epsilon::<T,T>();
+        let d1 = self.direction();
+        let d2 = other.direction();
+        let r = self.start - other.start;
+        let a = d1.magnitude2();
+        let e = d2.magnitude2();
+        let f = d2.dot(&r);
+
+        let (s, t) = if a <= eps && e <= eps {
+            // both segments degenerate into points
+            (zero!(T), zero!(T))
+        } else if a <= eps {
+            // `self` degenerates into a point
+            (zero!(T), (f / e).clamp(&zero!(T), &one!(T)))
+        } else {
+            let c = d1.dot(&r);
+            if e <= eps {
+                // `other` degenerates into a point
+                ((-c / a).clamp(&zero!(T), &one!(T)), zero!(T))
+            } else {
+                let b = d1.dot(&d2);
+                let denom = a * e - b * b;
+
+                // pick an arbitrary point on `self` if the segments are parallel
+                let s = if denom != zero!(T) {
+                    ((b * f - c * e) / denom).clamp(&zero!(T), &one!(T))
+                } else {
+                    zero!(T)
+                };
+
+                // find the closest point on `other` to the point on `self`,
+                // recomputing the point on `self` if it had to be clamped
+                let t = (b * s + f) / e;
+                if t < zero!(T) {
+                    ((-c / a).clamp(&zero!(T), &one!(T)), zero!(T))
+                } else if t > one!(T) {
+                    (((b - c) / a).clamp(&zero!(T), &one!(T)), one!(T))
+                } else {
+                    (s, t)
+                }
+            }
+        };
+
+        (self.point_at(s), other.point_at(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use math::point::*;
+    use math::segment::*;
+
+    #[test]
+    fn test_segment2_closest_point() {
+        let s = Segment2::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0));
+
+        assert_eq!(s.length(), 4.0);
+        assert_eq!(s.point_at(0.25), Point2::new(1.0, 0.0));
+        assert_eq!(s.closest_point(&Point2::new(3.0, 2.0)), Point2::new(3.0, 0.0));
+        assert_eq!(s.closest_point(&Point2::new(-1.0, 1.0)), Point2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_segment3_closest_point() {
+        let s = Segment3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 2.0));
+
+        assert_eq!(s.length2(), 4.0);
+        assert_eq!(s.length(), 2.0);
+        assert_eq!(s.point_at(0.5), Point3::new(0.0, 0.0, 1.0));
+
+        // interior
+        assert_eq!(s.closest_point(&Point3::new(1.0, 1.0, 1.5)), Point3::new(0.0, 0.0, 1.5));
+        // clamped to the endpoints
+        assert_eq!(s.closest_point(&Point3::new(1.0, 0.0, -3.0)), Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(s.closest_point(&Point3::new(0.0, 1.0, 5.0)), Point3::new(0.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn test_segment3_closest_points() {
+        // crossing
+        let a = Segment3::new(Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let b = Segment3::new(Point3::new(0.0, -1.0, 1.0), Point3::new(0.0, 1.0, 1.0));
+        assert_eq!(a.closest_points(&b), (Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 1.0)));
+
+        // parallel
+        let a = Segment3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 0.0, 0.0));
+        let b = Segment3::new(Point3::new(1.0, 1.0, 0.0), Point3::new(3.0, 1.0, 0.0));
+        let (p, q) = a.closest_points(&b);
+        assert_eq!(p.distance(&q), 1.0);
+        assert_eq!(p, Point3::new(1.0, 0.0, 0.0));
+
+        // skew, with both closest points at endpoints
+        let a = Segment3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let b = Segment3::new(Point3::new(2.0, 1.0, 1.0), Point3::new(2.0, 3.0, 1.0));
+        assert_eq!(a.closest_points(&b), (Point3::new(1.0, 0.0, 0.0), Point3::new(2.0, 1.0, 1.0)));
+
+        // degenerate
+        let p = Segment3::new(Point3::new(1.0, 1.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+        assert_eq!(p.closest_points(&a), (Point3::new(1.0, 1.0, 0.0), Point3::new(1.0, 0.0, 0.0)));
+        assert_eq!(a.closest_points(&p), (Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2013 The Lmath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Triangle primitive
+
+use math::{Point3, Vec3};
+use math::Ray3;
+
+/// A triangle in three dimensions, formed from the vertices `a`, `b` and `c`
+#[deriving(Clone, Eq)]
+pub struct Triangle3<T> {
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+}
+
+impl_approx!(Triangle3 { a, b, c })
+
+impl<T> Triangle3<T> {
+    #[inline]
+    pub fn new(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> Triangle3<T> {
+        Triangle3 { a: a, b: b, c: c }
+    }
+}
+
+impl<T:Clone + Float> Triangle3<T> {
+    /// The unnormalized normal of the triangle, with a magnitude of twice its
+    /// area
+    #[inline]
+    fn cross(&self) -> Vec3<T> {
+        (self.b - self.a).cross(&(self.c - self.a))
+    }
+
+    /// The unit normal of the triangle. This points towards the side from
+    /// which the vertices appear counter-clockwise.
+    #[inline]
+    pub fn normal(&self) -> Vec3<T> {
+        self.cross().normalize()
+    }
+
+    /// The area of the triangle
+    #[inline]
+    pub fn area(&self) -> T {
+        self.cross().magnitude() / two!(T)
+    }
+
+    /// Computes the intersection of `ray` with the triangle using the
+    /// Möller–Trumbore algorithm. Both the front and back faces of the
+    /// triangle are hit.
+    ///
+    /// # Return value
+    ///
+    /// - `Some((t, w0, w1, w2))`: The ray parameter `t` of the hit, and the
+    ///                            barycentric coordinates of the hit point,
+    ///                            such that `ray.point_at(t)` is equal to
+    ///                            `w0 * a + w1 * b + w2 * c`.
+    /// - `None`:                  The ray misses the triangle, or is
+    ///                            parallel to it.
+    pub fn intersection_ray(&self, ray: &Ray3<T>) -> Option<(T, T, T, T)> {
+        let edge1 = self.b - self.a;
+        let edge2 = self.c - self.a;
+
+        let p = ray.direction.cross(&edge2);
+        let det = edge1.dot(&p);
+
+        // the ray is parallel to the plane of the triangle
+        if det.approx_eq(&zero!(T)) { return None; }
+        let inv_det = det.recip();
+
+        let s = ray.origin - self.a;
+        let u = s.dot(&p) * inv_det;
+        if u < zero!(T) || u > one!(T) { return None; }
+
+        let q = s.cross(&edge1);
+        let v = ray.direction.dot(&q) * inv_det;
+        if v < zero!(T) || u + v > one!(T) { return None; }
+
+        let t = edge2.dot(&q) * inv_det;
+        if t < zero!(T) { return None; }
+
+        Some((t, one!(T) - u - v, u, v))
+    }
+
+    /// Returns the point on the triangle closest to `p`. See
+    /// `closest_point_on_triangle`.
+    #[inline]
+    pub fn closest_point(&self, p: &Point3<T>) -> Point3<T> {
+        closest_point_on_triangle(&self.a, &self.b, &self.c, p)
+    }
+}
+
+/// Computes the point on the triangle `abc` that is closest to `p`.
+///
+/// This finds which of the Voronoi regions of the vertices, edges and face of
+/// the triangle contains `p`, and projects `p` onto that feature, as described
+/// in Ericson's "Real-Time Collision Detection", section 5.1.5.
+pub fn closest_point_on_triangle<T:Clone + Float>(a: &Point3<T>, b: &Point3<T>,
+                                                  c: &Point3<T>, p: &Point3<T>) -> Point3<T> {
+    let ab = *b - *a;
+    let ac = *c - *a;
+
+    // the vertex region of `a`
+    let ap = *p - *a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= zero!(T) && d2 <= zero!(T) { return a.clone(); }
+
+    // the vertex region of `b`
+    let bp = *p - *b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= zero!(T) && d4 <= d3 { return b.clone(); }
+
+    // the edge region of `ab`
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= zero!(T) && d1 >= zero!(T) && d3 <= zero!(T) {
+        return *a + ab.mul_s(d1 / (d1 - d3));
+    }
+
+    // the vertex region of `c`
+    let cp = *p - *c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= zero!(T) && d5 <= d6 { return c.clone(); }
+
+    // the edge region of `ac`
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= zero!(T) && d2 >= zero!(T) && d6 <= zero!(T) {
+        return *a + ac.mul_s(d2 / (d2 - d6));
+    }
+
+    // the edge region of `bc`
+    let va = d3 * d6 - d5 * d4;
+    if va <= zero!(T) && d4 - d3 >= zero!(T) && d5 - d6 >= zero!(T) {
+        return *b + (*c - *b).mul_s((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // the face region, with `p` projected using its barycentric coordinates
+    let denom = (va + vb + vc).recip();
+    *a + ab.mul_s(vb * denom).add_v(&ac.mul_s(vc * denom))
+}
+
+#[cfg(test)]
+mod tests {
+    use math::point::*;
+    use math::ray::*;
+    use math::triangle::*;
+    use math::vec::*;
+
+    #[test]
+    fn test_normal_area() {
+        let tri = Triangle3::new(Point3::new(0.0, 0.0, 0.0),
+                                 Point3::new(2.0, 0.0, 0.0),
+                                 Point3::new(0.0, 2.0, 0.0));
+
+        assert_eq!(tri.normal(), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(tri.area(), 2.0);
+    }
+
+    #[test]
+    fn test_intersection_ray() {
+        let tri = Triangle3::new(Point3::new(0.0, 0.0, 0.0),
+                                 Point3::new(1.0, 0.0, 0.0),
+                                 Point3::new(0.0, 1.0, 0.0));
+        let third = 1.0 / 3.0;
+
+        // through the centroid, from the front
+        let ray = Ray3::new(Point3::new(third, third, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let (t, w0, w1, w2) = tri.intersection_ray(&ray).unwrap();
+        assert_approx_eq!(t, 1.0);
+        assert_approx_eq!(w0, third);
+        assert_approx_eq!(w1, third);
+        assert_approx_eq!(w2, third);
+
+        // through the centroid, from the back
+        let ray = Ray3::new(Point3::new(third, third, -2.0), Vec3::new(0.0, 0.0, 1.0));
+        let (t, _, _, _) = tri.intersection_ray(&ray).unwrap();
+        assert_approx_eq!(t, 2.0);
+
+        // outside of the triangle
+        assert_eq!(tri.intersection_ray(&Ray3::new(Point3::new(1.0, 1.0, 1.0),
+                                                   Vec3::new(0.0, 0.0, -1.0))), None);
+        // pointing away from the triangle
+        assert_eq!(tri.intersection_ray(&Ray3::new(Point3::new(third, third, 1.0),
+                                                   Vec3::new(0.0, 0.0, 1.0))), None);
+        // parallel to the triangle
+        assert_eq!(tri.intersection_ray(&Ray3::new(Point3::new(-1.0, third, 0.0),
+                                                   Vec3::new(1.0, 0.0, 0.0))), None);
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let tri = Triangle3::new(Point3::new(0.0, 0.0, 0.0),
+                                 Point3::new(2.0, 0.0, 0.0),
+                                 Point3::new(0.0, 2.0, 0.0));
+
+        // vertices
+        assert_eq!(tri.closest_point(&Point3::new(-1.0, -1.0, 1.0)), Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(tri.closest_point(&Point3::new(3.0, -1.0, 0.0)), Point3::new(2.0, 0.0, 0.0));
+        assert_eq!(tri.closest_point(&Point3::new(-1.0, 4.0, -2.0)), Point3::new(0.0, 2.0, 0.0));
+
+        // edges
+        assert_eq!(tri.closest_point(&Point3::new(1.0, -1.0, 5.0)), Point3::new(1.0, 0.0, 0.0));
+        assert_eq!(tri.closest_point(&Point3::new(-3.0, 0.5, 0.0)), Point3::new(0.0, 0.5, 0.0));
+        assert_eq!(tri.closest_point(&Point3::new(2.0, 2.0, 0.0)), Point3::new(1.0, 1.0, 0.0));
+
+        // face
+        assert_eq!(tri.closest_point(&Point3::new(0.5, 0.5, 3.0)), Point3::new(0.5, 0.5, 0.0));
+        assert_eq!(closest_point_on_triangle(&tri.a, &tri.b, &tri.c, &Point3::new(0.5, 0.25, -1.0)),
+                   Point3::new(0.5, 0.25, 0.0));
+    }
+}
@@ -15,7 +15,7 @@
 
 //! Abstract vector types
 
-use math::{Dimensioned, SwapComponents};
+use math::{BitEq, Dimensioned, SwapComponents};
 
 /// Generic vector trait
 pub trait Vec<T,Slice>: Dimensioned<T,Slice>
@@ -144,6 +144,7 @@ pub trait AsVec2<T> {
 impl_dimensioned!(Vec2, T, 2)
 impl_swap_components!(Vec2)
 impl_approx!(Vec2 { x, y })
+impl_bit_eq!(Vec2 { x, y })
 
 impl<T> Vec2<T> {
     /// Construct a new vector from the supplied components.
@@ -413,8 +414,8 @@ impl<T:Float> FloatVec<T,[T,..2]> for Vec2<T> {
         self.mul_s(magnitude / self.magnitude())
     }
 
-    /// Returns the result of linarly interpolating the magnitude of the vector
-    /// to the magnitude of `other` by the specified amount.
+    /// Returns the result of linearly interpolating from the vector towards
+    /// `other` by the specified amount: `self + (other - self) * amount`.
     #[inline]
     pub fn lerp(&self, other: &Vec2<T>, amount: T) -> Vec2<T> {
         self.add_v(&other.sub_v(self).mul_s(amount))
@@ -434,8 +435,8 @@ impl<T:Float> FloatVec<T,[T,..2]> for Vec2<T> {
         self.mul_self_s(n);
     }
 
-    /// Linearly interpolates the magnitude of the vector to the magnitude of
-    /// `other` by the specified amount.
+    /// Linearly interpolates the vector towards `other` by the specified
+    /// amount.
     pub fn lerp_self(&mut self, other: &Vec2<T>, amount: T) {
         let v = other.sub_v(self).mul_s(amount);
         self.add_self_v(&v);
@@ -705,6 +706,16 @@ mod vec2_tests {
         assert_eq!(mut_c, c.lerp(&d, 0.75));
     }
 
+    #[test]
+    fn test_lerp_endpoints() {
+        let c = Vec2::new::<float>(-2.0, -1.0);
+        let d = Vec2::new::<float>( 1.0,  0.0);
+
+        assert_eq!(c.lerp(&d, 0.0), c);
+        assert_eq!(c.lerp(&d, 0.5), Vec2::new::<float>(-0.5, -0.5));
+        assert_eq!(c.lerp(&d, 1.0), d);
+    }
+
     #[test]
     fn test_comp_min() {
         assert_eq!(A.comp_min(), 1.0);
@@ -776,6 +787,7 @@ pub trait AsVec3<T> {
 impl_dimensioned!(Vec3, T, 3)
 impl_swap_components!(Vec3)
 impl_approx!(Vec3 { x, y, z })
+impl_bit_eq!(Vec3 { x, y, z })
 
 impl<T> Vec3<T> {
     /// Construct a new vector from the supplied components.
@@ -1084,8 +1096,8 @@ impl<T:Float> FloatVec<T,[T,..3]> for Vec3<T> {
         self.mul_s(magnitude / self.magnitude())
     }
 
-    /// Returns the result of linarly interpolating the magnitude of the vector
-    /// to the magnitude of `other` by the specified amount.
+    /// Returns the result of linearly interpolating from the vector towards
+    /// `other` by the specified amount: `self + (other - self) * amount`.
     #[inline]
     pub fn lerp(&self, other: &Vec3<T>, amount: T) -> Vec3<T> {
         self.add_v(&other.sub_v(self).mul_s(amount))
@@ -1105,14 +1117,104 @@ impl<T:Float> FloatVec<T,[T,..3]> for Vec3<T> {
         self.mul_self_s(n);
     }
 
-    /// Linearly interpolates the magnitude of the vector to the magnitude of
-    /// `other` by the specified amount.
+    /// Linearly interpolates the vector towards `other` by the specified
+    /// amount.
     pub fn lerp_self(&mut self, other: &Vec3<T>, amount: T) {
         let v = other.sub_v(self).mul_s(amount);
         self.add_self_v(&v);
     }
 }
 
+impl<T:Float> Vec3<T> {
+    /// Returns the normalized vector, or `None` if the vector is too close
+    /// to zero length to have a meaningful direction.
+    #[inline]
+    pub fn try_normalize(&self) -> Option<Vec3<T>> {
+        let magnitude = self.magnitude();
+        if magnitude.approx_eq(&zero!(T)) {
+            None
+        } else {
+            Some(self.div_s(magnitude))
+        }
+    }
+
+    /// Returns the component of the vector that is parallel to `onto`.
+    #[inline]
+    pub fn project_onto(&self, onto: &Vec3<T>) -> Vec3<T> {
+        onto.mul_s(self.dot(onto) / onto.dot(onto))
+    }
+
+    /// Returns the component of the vector that is perpendicular to `onto`,
+    /// such that `v.project_onto(onto) + v.reject_from(onto) == v`.
+    #[inline]
+    pub fn reject_from(&self, onto: &Vec3<T>) -> Vec3<T> {
+        self.sub_v(&self.project_onto(onto))
+    }
+
+    /// Spherically interpolates between the unit direction vectors `self` and
+    /// `other`, following the great circle between them.
+    ///
+    /// Falls back to a normalized linear interpolation when the directions
+    /// are nearly parallel. The path is undefined for opposite directions.
+    pub fn slerp(&self, other: &Vec3<T>, amount: T) -> Vec3<T> {
+        let theta = self.angle(other);
+        let sin_theta = theta.sin();
+
+        if sin_theta.approx_eq(&zero!(T)) {
+            self.lerp(other, amount).normalize()
+        } else {
+            self.mul_s(((one!(T) - amount) * theta).sin())
+                .add_v(&other.mul_s((amount * theta).sin()))
+                .div_s(sin_theta)
+        }
+    }
+
+    /// Reflects the vector about a surface with the unit length `normal`.
+    #[inline]
+    pub fn reflect(&self, normal: &Vec3<T>) -> Vec3<T> {
+        self.sub_v(&normal.mul_s(two!(T) * self.dot(normal)))
+    }
+
+    /// Returns the index of the component with the largest magnitude, where
+    /// `0`, `1` and `2` correspond to the `x`, `y` and `z` axes. Ties are
+    /// resolved in favour of the lower index.
+    pub fn dominant_axis(&self) -> uint {
+        let (x, y, z) = (self.x.abs(), self.y.abs(), self.z.abs());
+        if x >= y && x >= z {
+            0
+        } else if y >= z {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Returns the signed axis-aligned unit vector closest in direction to
+    /// the vector, for example `-Vec3::unit_y()` for `(0.1, -2, 0.5)`.
+    pub fn major_direction(&self) -> Vec3<T> {
+        let axis = self.dominant_axis();
+        let mut dir = Vec3::zero::<T>();
+        *dir.mut_i(axis) = if *self.i(axis) < zero!(T) { -one!(T) } else { one!(T) };
+        dir
+    }
+
+    /// Constructs two unit vectors that are orthogonal to the unit length
+    /// vector and to each other, such that `t.cross(&b) == *self` for the
+    /// returned `(t, b)`.
+    ///
+    /// This uses the branchless construction from Duff et al., "Building an
+    /// Orthonormal Basis, Revisited", which remains accurate for all inputs,
+    /// including those aligned with an axis.
+    pub fn build_basis(&self) -> (Vec3<T>, Vec3<T>) {
+        let sign = if self.z < zero!(T) { -one!(T) } else { one!(T) };
+        let a = -one!(T) / (sign + self.z);
+        let b = self.x * self.y * a;
+
+        (Vec3::new(one!(T) + sign * self.x * self.x * a, sign * b, -sign * self.x),
+         Vec3::new(b, sign + self.y * self.y * a, -self.y))
+    }
+}
+
 impl<T:Orderable> OrdVec<T,[T,..3],Vec3<bool>> for Vec3<T> {
     #[inline]
     pub fn lt_s(&self, value: T) -> Vec3<bool> {
@@ -1279,6 +1381,20 @@ impl<T:Not<T>> Not<Vec3<T>> for Vec3<T> {
     }
 }
 
+/// Orthonormalizes a set of basis vectors using the Gram-Schmidt process,
+/// making them suitable for `Mat3::from_axes` and `Quat::from_axes`.
+///
+/// The direction of `x` is preserved, `y` is made perpendicular to `x`, and
+/// `z` is made perpendicular to both. The input vectors should be linearly
+/// independent.
+pub fn orthonormalize<T:Float>(x: &Vec3<T>, y: &Vec3<T>, z: &Vec3<T>) -> (Vec3<T>, Vec3<T>, Vec3<T>) {
+    let x_ = x.normalize();
+    let y_ = y.sub_v(&x_.mul_s(x_.dot(y))).normalize();
+    let z_ = z.sub_v(&x_.mul_s(x_.dot(z)))
+              .sub_v(&y_.mul_s(y_.dot(z))).normalize();
+    (x_, y_, z_)
+}
+
 #[cfg(test)]
 mod vec3_tests{
     use math::vec::*;
@@ -1411,6 +1527,16 @@ mod vec3_tests{
         assert_eq!(mut_c, c.lerp(&d, 0.75));
     }
 
+    #[test]
+    fn test_lerp_endpoints() {
+        let c = Vec3::new::<float>(-2.0, -1.0, 1.0);
+        let d = Vec3::new::<float>( 1.0,  0.0, 0.5);
+
+        assert_eq!(c.lerp(&d, 0.0), c);
+        assert_eq!(c.lerp(&d, 0.5), Vec3::new::<float>(-0.5, -0.5, 0.75));
+        assert_eq!(c.lerp(&d, 1.0), d);
+    }
+
     #[test]
     fn test_comp_min() {
         assert_eq!(A.comp_min(), 1.0);
@@ -1441,6 +1567,91 @@ mod vec3_tests{
         assert_eq!(ttt.all(), true);
         assert_eq!(!ttt, Vec3::new(false, false, false));
     }
+
+    #[test]
+    fn test_orthonormalize() {
+        let (x, y, z) = orthonormalize(&Vec3::new::<float>(1.0,  0.02, -0.01),
+                                       &Vec3::new::<float>(0.03, 0.98,  0.01),
+                                       &Vec3::new::<float>(0.0, -0.04,  1.05));
+
+        assert_approx_eq!(x.magnitude(), 1.0);
+        assert_approx_eq!(y.magnitude(), 1.0);
+        assert_approx_eq!(z.magnitude(), 1.0);
+        assert_approx_eq!(x.dot(&y), 0.0);
+        assert_approx_eq!(x.dot(&z), 0.0);
+        assert_approx_eq!(y.dot(&z), 0.0);
+        assert_approx_eq!(x.cross(&y), z);
+        assert_approx_eq!(x, Vec3::new::<float>(1.0, 0.02, -0.01).normalize());
+    }
+
+    #[test]
+    fn test_project_onto() {
+        let onto = Vec3::new::<float>(0.0, 2.0, 0.0);
+
+        assert_eq!(A.project_onto(&onto), Vec3::new::<float>(0.0, 2.0, 0.0));
+        assert_eq!(A.reject_from(&onto), Vec3::new::<float>(1.0, 0.0, 3.0));
+        assert_approx_eq!(B.project_onto(&A).add_v(&B.reject_from(&A)), B);
+        assert_approx_eq!(B.reject_from(&A).dot(&A), 0.0);
+        assert_eq!(Vec3::new::<float>(1.0, 0.0, 1.0).project_onto(&onto), Vec3::zero());
+    }
+
+    #[test]
+    fn test_reflect() {
+        let normal = Vec3::new::<float>(0.0, 1.0, 0.0);
+
+        assert_eq!(Vec3::new::<float>(1.0, -2.0, 3.0).reflect(&normal), Vec3::new::<float>(1.0, 2.0, 3.0));
+        assert_eq!(Vec3::new::<float>(1.0, 0.0, 0.0).reflect(&normal), Vec3::new::<float>(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_slerp() {
+        let x = Vec3::unit_x::<float>();
+        let y = Vec3::unit_y::<float>();
+        let half_sqrt2 = 0.5f.sqrt();
+
+        assert_approx_eq!(x.slerp(&y, 0.5), Vec3::new::<float>(half_sqrt2, half_sqrt2, 0.0));
+        assert_approx_eq!(x.slerp(&y, 0.0), x);
+        assert_approx_eq!(x.slerp(&y, 1.0), y);
+        assert_approx_eq!(x.slerp(&y, 0.5).magnitude(), 1.0);
+        assert_approx_eq!(x.slerp(&x, 0.5), x);
+    }
+
+    #[test]
+    fn test_dominant_axis() {
+        assert_eq!(Vec3::new::<float>(3.0, -1.0, 2.0).dominant_axis(), 0);
+        assert_eq!(Vec3::new::<float>(0.1, -2.0, 0.5).dominant_axis(), 1);
+        assert_eq!(Vec3::new::<float>(1.0, 1.0, -4.0).dominant_axis(), 2);
+        assert_eq!(Vec3::new::<float>(1.0, 1.0, 1.0).dominant_axis(), 0);
+
+        assert_eq!(Vec3::new::<float>(3.0, -1.0, 2.0).major_direction(), Vec3::unit_x());
+        assert_eq!(Vec3::new::<float>(0.1, -2.0, 0.5).major_direction(), -Vec3::unit_y::<float>());
+        assert_eq!(Vec3::new::<float>(1.0, 1.0, -4.0).major_direction(), -Vec3::unit_z::<float>());
+    }
+
+    #[test]
+    fn test_try_normalize() {
+        assert_eq!(Vec3::zero::<float>().try_normalize(), None);
+        assert_eq!(Vec3::new::<float>(2.0, 3.0, 6.0).try_normalize(),
+                   Some(Vec3::new::<float>(2.0/7.0, 3.0/7.0, 6.0/7.0)));
+    }
+
+    #[test]
+    fn test_build_basis() {
+        let dirs = [Vec3::unit_x::<float>(), Vec3::unit_y::<float>(),
+                    Vec3::unit_z::<float>(), -Vec3::unit_z::<float>(),
+                    Vec3::new::<float>(1.0, 2.0, 3.0).normalize(),
+                    Vec3::new::<float>(-4.0, 1.0, -2.0).normalize()];
+
+        for dirs.iter().advance |n| {
+            let (t, b) = n.build_basis();
+            assert_approx_eq!(t.magnitude(), 1.0);
+            assert_approx_eq!(b.magnitude(), 1.0);
+            assert_approx_eq!(t.dot(n), 0.0);
+            assert_approx_eq!(b.dot(n), 0.0);
+            assert_approx_eq!(t.dot(&b), 0.0);
+            assert_approx_eq!(t.cross(&b), *n);
+        }
+    }
 }
 
 #[deriving(Clone, Eq)]
@@ -1482,6 +1693,7 @@ pub trait AsVec4<T> {
 impl_dimensioned!(Vec4, T, 4)
 impl_swap_components!(Vec4)
 impl_approx!(Vec4 { x, y, z, w })
+impl_bit_eq!(Vec4 { x, y, z, w })
 
 impl<T> Vec4<T> {
     /// Construct a new vector from the supplied components.
@@ -1791,8 +2003,8 @@ impl<T:Float> FloatVec<T,[T,..4]> for Vec4<T> {
         self.mul_s(magnitude / self.magnitude())
     }
 
-    /// Returns the result of linarly interpolating the magnitude of the vector
-    /// to the magnitude of `other` by the specified amount.
+    /// Returns the result of linearly interpolating from the vector towards
+    /// `other` by the specified amount: `self + (other - self) * amount`.
     #[inline]
     pub fn lerp(&self, other: &Vec4<T>, amount: T) -> Vec4<T> {
         self.add_v(&other.sub_v(self).mul_s(amount))
@@ -1812,8 +2024,8 @@ impl<T:Float> FloatVec<T,[T,..4]> for Vec4<T> {
         self.mul_self_s(n);
     }
 
-    /// Linearly interpolates the magnitude of the vector to the magnitude of
-    /// `other` by the specified amount.
+    /// Linearly interpolates the vector towards `other` by the specified
+    /// amount.
     pub fn lerp_self(&mut self, other: &Vec4<T>, amount: T) {
         let v = other.sub_v(self).mul_s(amount);
         self.add_self_v(&v);
@@ -2128,6 +2340,16 @@ mod vec4_tests {
         assert_eq!(mut_c, c.lerp(&d, 0.75));
     }
 
+    #[test]
+    fn test_lerp_endpoints() {
+        let c = Vec4::new::<float>(-2.0, -1.0, 1.0, 2.0);
+        let d = Vec4::new::<float>( 1.0,  0.0, 0.5, 1.0);
+
+        assert_eq!(c.lerp(&d, 0.0), c);
+        assert_eq!(c.lerp(&d, 0.5), Vec4::new::<float>(-0.5, -0.5, 0.75, 1.5));
+        assert_eq!(c.lerp(&d, 1.0), d);
+    }
+
     #[test]
     fn test_comp_min() {
         assert_eq!(A.comp_min(), 1.0);
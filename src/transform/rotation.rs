@@ -307,9 +307,61 @@ impl<T:Float> ToMat4<T> for Euler<T> {
     }
 }
 
+impl<T:Clone + Float> ToEuler<T> for Quat<T> {
+    /// Decomposes the quaternion into the Euler angles accepted by
+    /// `Euler::to_quat`, such that the rotation is formed from the `roll`,
+    /// followed by the `pitch`, followed by the `yaw`.
+    ///
+    /// When the pitch approaches a quarter turn, the roll and the yaw rotate
+    /// about the same axis and can no longer be told apart (gimbal lock). In
+    /// that case the roll is set to zero and the combined rotation is stored
+    /// in the yaw.
+    pub fn to_euler(&self) -> Euler<T> {
+        // http://en.wikipedia.org/wiki/Conversion_between_quaternions_and_Euler_angles#Conversion
+        let (w, x, y, z) = (self.s.clone(), self.v.x.clone(), self.v.y.clone(), self.v.z.clone());
+        let sin_pitch = two!(T) * (w * y - z * x);
+
+        if sin_pitch.abs() >= one!(T) - ApproxEq::approx_epsilon::<T,T>() {
+            // gimbal lock
+            let sign = sin_pitch.signum();
+            Euler::new(sign * Real::frac_pi_2(),
+                       -sign * two!(T) * x.atan2(&w),
+                       zero!(T))
+        } else {
+            Euler::new(sin_pitch.asin(),
+                       (two!(T) * (w * z + x * y)).atan2(&(one!(T) - two!(T) * (y * y + z * z))),
+                       (two!(T) * (w * x + y * z)).atan2(&(one!(T) - two!(T) * (x * x + y * y))))
+        }
+    }
+}
+
 #[cfg(test)]
 mod euler_tests {
-    // TODO
+    use math::*;
+    use transform::*;
+
+    fn assert_same_rotation(a: &Quat<float>, b: &Quat<float>) {
+        assert!(a.approx_eq(b) || a.approx_eq(&-*b));
+    }
+
+    #[test]
+    fn test_quat_to_euler() {
+        let eulers = [Euler::new(0.3, -1.2, 0.5),
+                      Euler::new(-1.0, 2.5, -2.0),
+                      Euler::new(0.0, 0.0, 0.0),
+                      Euler::new(1.56, 0.4, 0.7)];     // close to gimbal lock
+        for eulers.iter().advance |e| {
+            let q = e.to_quat();
+            assert_approx_eq!(q.to_euler(), *e);
+            assert_same_rotation(&q.to_euler().to_quat(), &q);
+        }
+
+        // at gimbal lock the roll is folded into the yaw
+        let q = Euler::new(Real::frac_pi_2::<float>(), 0.4, 0.7).to_quat();
+        let e = q.to_euler();
+        assert_eq!(e.roll, 0.0);
+        assert_same_rotation(&e.to_quat(), &q);
+    }
 }
 
 /// A rotation about an arbitrary axis
@@ -407,6 +459,13 @@ impl<T:Float> ToMat4<T> for AxisAngle<T> {
     }
 }
 
+impl<T:Clone + Float> ToAxisAngle<T> for Quat<T> {
+    #[inline]
+    pub fn to_axis_angle(&self) -> AxisAngle<T> {
+        AxisAngle::new(self.axis(), self.angle())
+    }
+}
+
 #[cfg(test)]
 mod axis_angle_tests {
     use math::*;
@@ -425,6 +484,26 @@ mod axis_angle_tests {
                                                  -1f/2f.sqrt(), 1f/2f.sqrt(), 0f,
                                                             0f,           0f, 1f));
     }
+
+    #[test]
+    fn test_from_quat() {
+        let axis = Vec3::new(2f, 3f, 6f).normalize();
+        let q = AxisAngle::new(axis, 1.25f).to_quat();
+        let aa = q.to_axis_angle();
+
+        assert_approx_eq!(aa, AxisAngle::new(axis, 1.25f));
+        assert_approx_eq!(q.axis(), Vec3::new(2f / 7f, 3f / 7f, 6f / 7f));
+        assert_approx_eq!(q.angle(), 1.25f);
+
+        let identity = Quat::identity::<float>();
+        assert_eq!(identity.axis(), Vec3::unit_x());
+        assert_eq!(identity.angle(), 0f);
+
+        // a small rotation still has a well defined axis
+        let small = Quat::rotation_z::<float>(1.0e-3);
+        assert_approx_eq!(small.axis(), Vec3::unit_z());
+        assert_approx_eq!(small.to_axis_angle(), AxisAngle::new(Vec3::unit_z(), 1.0e-3));
+    }
 }
 
 /// An angle around the X axis (pitch), in radians.
@@ -19,17 +19,201 @@ pub use self::rotation::{Euler, ToEuler};
 pub use self::rotation::{AxisAngle, ToAxisAngle};
 pub use self::rotation::{AngleX, AngleY, AngleZ};
 
-use math::{Vec3, Quat};
+use math::{Mat3, Mat4, ToMat3, ToMat4};
+use math::{Point3, Vec3, Vec4, Quat};
 
 pub mod projection;
 pub mod rotation;
 
 pub trait Transform<T> {}
 
-pub struct QuatTransform<T> {
+/// A transformation composed of a uniform scale, followed by a rotation,
+/// followed by a translation.
+#[deriving(Clone, Eq)]
+pub struct Transform3<T> {
+    translation: Vec3<T>,
+    rotation: Quat<T>,
     scale: T,
+}
+
+pub type QuatTransform<T> = Transform3<T>;
+
+impl_approx!(Transform3 { translation, rotation, scale })
+
+impl<T> Transform3<T> {
+    #[inline]
+    pub fn new(translation: Vec3<T>, rotation: Quat<T>, scale: T) -> Transform3<T> {
+        Transform3 { translation: translation, rotation: rotation, scale: scale }
+    }
+}
+
+impl<T:Clone + Float> Transform3<T> {
+    /// The transformation that leaves points unchanged
+    #[inline]
+    pub fn identity() -> Transform3<T> {
+        Transform3::new(Vec3::zero(), Quat::identity(), one!(T))
+    }
+
+    /// Scales, rotates, then translates `point`
+    pub fn transform_point(&self, point: &Point3<T>) -> Point3<T> {
+        Point3::from_vec3(self.rotation.mul_v(&point.as_vec3().mul_s(self.scale.clone()))
+                                       .add_v(&self.translation))
+    }
+
+    /// Scales then rotates `vec`. Vectors are not affected by the translation.
+    pub fn transform_vec(&self, vec: &Vec3<T>) -> Vec3<T> {
+        self.rotation.mul_v(&vec.mul_s(self.scale.clone()))
+    }
+
+    /// The transformation that undoes `self`
+    pub fn inverse(&self) -> Transform3<T> {
+        let scale = one!(T) / self.scale;
+        let rotation = self.rotation.inverse();
+        let translation = rotation.mul_v(&self.translation).mul_s(-scale);
+
+        Transform3::new(translation, rotation, scale)
+    }
+
+    /// The transformation that applies `other`, followed by `self`
+    pub fn concat(&self, other: &Transform3<T>) -> Transform3<T> {
+        Transform3::new(self.transform_vec(&other.translation).add_v(&self.translation),
+                        self.rotation.mul_q(&other.rotation),
+                        self.scale * other.scale)
+    }
+}
+
+impl<T> Transform<T> for Transform3<T> {}
+
+/// A transformation composed of a possibly non-uniform scale along each axis,
+/// followed by a rotation, followed by a translation.
+#[deriving(Clone, Eq)]
+pub struct TransformSrt3<T> {
     translation: Vec3<T>,
     rotation: Quat<T>,
+    scale: Vec3<T>,
+}
+
+impl_approx!(TransformSrt3 { translation, rotation, scale })
+
+impl<T> TransformSrt3<T> {
+    #[inline]
+    pub fn new(translation: Vec3<T>, rotation: Quat<T>, scale: Vec3<T>) -> TransformSrt3<T> {
+        TransformSrt3 { translation: translation, rotation: rotation, scale: scale }
+    }
 }
 
-impl<T> Transform<T> for QuatTransform<T> {}
+impl<T:Clone + Float> TransformSrt3<T> {
+    /// The transformation that leaves points unchanged
+    #[inline]
+    pub fn identity() -> TransformSrt3<T> {
+        TransformSrt3::new(Vec3::zero(), Quat::identity(), Vec3::from_value(one!(T)))
+    }
+
+    /// Scales, rotates, then translates `point`
+    pub fn transform_point(&self, point: &Point3<T>) -> Point3<T> {
+        Point3::from_vec3(self.transform_vec(point.as_vec3()).add_v(&self.translation))
+    }
+
+    /// Scales then rotates `vec`. Vectors are not affected by the translation.
+    pub fn transform_vec(&self, vec: &Vec3<T>) -> Vec3<T> {
+        self.rotation.mul_v(&vec.mul_v(&self.scale))
+    }
+
+    /// The transformation that undoes `self`, as the affine matrix
+    /// `S⁻¹R⁻¹T⁻¹`.
+    ///
+    /// Unlike `Transform3::inverse`, the result is not another
+    /// `TransformSrt3`. When a rotation is combined with a non-uniform scale,
+    /// the inverse applies the scale after the rotation, which cannot in
+    /// general be expressed as a scale followed by a rotation.
+    pub fn inverse(&self) -> Mat4<T> {
+        let inv_scale = Vec3::from_value(one!(T)).div_v(&self.scale);
+        let rot = self.rotation.inverse().to_mat3();
+        // scaling after the rotation scales each row of the rotation matrix
+        let m = Mat3::from_cols(rot.c(0).mul_v(&inv_scale),
+                                rot.c(1).mul_v(&inv_scale),
+                                rot.c(2).mul_v(&inv_scale));
+        let t = -m.mul_v(&self.translation);
+
+        let mut mat = m.to_mat4();
+        *mat.mut_c(3) = Vec4::new(t.x.clone(), t.y.clone(), t.z.clone(), one!(T));
+        mat
+    }
+
+    /// Undoes the transformation of `point`, by translating, rotating, then
+    /// scaling it by the inverse of each part: `S⁻¹R⁻¹T⁻¹`. This is
+    /// equivalent to transforming `point` by `inverse`, without building the
+    /// matrix.
+    pub fn inverse_transform_point(&self, point: &Point3<T>) -> Point3<T> {
+        Point3::from_vec3(self.inverse_transform_vec(&point.as_vec3().sub_v(&self.translation)))
+    }
+
+    /// Undoes the transformation of `vec`, by rotating then scaling it by the
+    /// inverse of each part: `S⁻¹R⁻¹`.
+    pub fn inverse_transform_vec(&self, vec: &Vec3<T>) -> Vec3<T> {
+        self.rotation.inverse().mul_v(vec).div_v(&self.scale)
+    }
+}
+
+impl<T> Transform<T> for TransformSrt3<T> {}
+
+#[cfg(test)]
+mod tests {
+    use math::*;
+    use transform::*;
+
+    #[test]
+    fn test_inverse() {
+        let t = Transform3::new(Vec3::new::<float>(1.0, -2.0, 3.0),
+                                AxisAngle::new(Vec3::new(0.0, 0.0, 1.0), 0.5).to_quat(),
+                                2.0);
+        let p = Point3::new::<float>(4.0, 5.0, -6.0);
+
+        assert_approx_eq!(t.inverse().transform_point(&t.transform_point(&p)), p);
+        assert_approx_eq!(t.transform_point(&t.inverse().transform_point(&p)), p);
+    }
+
+    #[test]
+    fn test_concat() {
+        let a = Transform3::new(Vec3::new::<float>(1.0, 0.0, 0.0),
+                                AxisAngle::new(Vec3::new(0.0, 1.0, 0.0), 0.3).to_quat(),
+                                2.0);
+        let b = Transform3::new(Vec3::new::<float>(0.0, 2.0, -1.0),
+                                AxisAngle::new(Vec3::new(1.0, 0.0, 0.0), -1.2).to_quat(),
+                                0.5);
+        let p = Point3::new::<float>(4.0, 5.0, -6.0);
+
+        assert_approx_eq!(a.concat(&b).transform_point(&p),
+                          a.transform_point(&b.transform_point(&p)));
+        assert_approx_eq!(a.concat(&a.inverse()).transform_point(&p), p);
+    }
+
+    #[test]
+    fn test_srt_round_trip() {
+        let t = TransformSrt3::new(Vec3::new::<float>(1.0, -2.0, 3.0),
+                                   AxisAngle::new(Vec3::new(0.0, 0.0, 1.0), 0.5).to_quat(),
+                                   Vec3::new::<float>(2.0, 0.5, 3.0));
+        let p = Point3::new::<float>(4.0, 5.0, -6.0);
+        let v = Vec3::new::<float>(-1.0, 2.0, 0.5);
+
+        assert_approx_eq!(t.inverse_transform_point(&t.transform_point(&p)), p);
+        assert_approx_eq!(t.transform_point(&t.inverse_transform_point(&p)), p);
+        assert_approx_eq!(t.inverse_transform_vec(&t.transform_vec(&v)), v);
+
+        let inv = t.inverse();
+        assert_approx_eq!(inv.mul_v(&t.transform_point(&p).to_vec4()), p.to_vec4());
+        assert_approx_eq!(inv.mul_v(&p.to_vec4()), t.inverse_transform_point(&p).to_vec4());
+        assert_eq!(TransformSrt3::identity::<float>().transform_point(&p), p);
+    }
+
+    #[test]
+    fn test_srt_scale_before_rotation() {
+        let t = TransformSrt3::new(Vec3::zero::<float>(),
+                                   AxisAngle::new(Vec3::new(0.0, 0.0, 1.0), Real::frac_pi_2()).to_quat(),
+                                   Vec3::new::<float>(2.0, 1.0, 1.0));
+
+        // stretched along `x` first, then rotated onto `y`
+        assert_approx_eq!(t.transform_vec(&Vec3::new(1.0, 0.0, 0.0)), Vec3::new(0.0, 2.0, 0.0));
+        assert_approx_eq!(t.transform_vec(&Vec3::new(0.0, 1.0, 0.0)), Vec3::new(-1.0, 0.0, 0.0));
+    }
+}